@@ -0,0 +1,196 @@
+use std::{collections::HashMap, fs::File, io::BufReader, path::PathBuf};
+
+use clap::ValueEnum;
+use thiserror::Error;
+use typomania::checks::{
+    Affixes, Bitflips, Chain, Check, Delimiters, EditDistance, Generator, Homoglyphs, Inflections,
+    Leetspeak, Omitted, Phonetic, PhoneticEncoding, Repeated, Scoped, SwappedCharacters,
+    SwappedWords, Typos, Version,
+};
+
+use crate::Opt;
+
+/// The checks that can be enabled from the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Name {
+    Affixes,
+    Bitflips,
+    Chain,
+    Delimiters,
+    EditDistance,
+    Homoglyphs,
+    Inflections,
+    Leetspeak,
+    Omitted,
+    Phonetic,
+    Repeated,
+    Scoped,
+    SwappedCharacters,
+    SwappedWords,
+    Typos,
+    Version,
+}
+
+impl Name {
+    /// Instantiates the check, using `opt` for configuration and `names` as the names in the
+    /// corpus.
+    pub fn build(&self, opt: &Opt, names: &[&str]) -> Result<Box<dyn Check>, Error> {
+        Ok(match self {
+            Name::Affixes => Box::new(Affixes::default()),
+            Name::Bitflips => Box::new(Bitflips::new(&opt.alphabet, names.iter().copied())),
+            Name::Chain => {
+                let mut chain = Chain::new().with_max_depth(opt.chain_depth);
+                for name in opt.checks.iter() {
+                    if let Some(generator) = name.generator(opt)? {
+                        chain = chain.with_boxed_generator(generator);
+                    }
+                }
+                Box::new(chain)
+            }
+            Name::Delimiters => Box::new(Delimiters::new(&opt.delimiters, names.iter().copied())),
+            Name::EditDistance => Box::new(
+                EditDistance::new(opt.max_distance, names.iter().copied())
+                    .with_min_length(opt.min_length),
+            ),
+            Name::Homoglyphs => Box::new(Homoglyphs::new(names.iter().copied())),
+            Name::Inflections => Box::new(Inflections::new(&opt.delimiters)),
+            Name::Leetspeak => Box::new(Leetspeak::default()),
+            Name::Omitted => Box::new(Omitted::new(&opt.alphabet)),
+            Name::Phonetic => Box::new(Phonetic::new(
+                opt.phonetic.encoding(),
+                &opt.delimiters,
+                names.iter().copied(),
+            )),
+            Name::Repeated => Box::new(Repeated),
+            Name::Scoped => {
+                // Checks run by the scoped check only see one part of each name at a time.
+                let parts: Vec<&str> = names
+                    .iter()
+                    .flat_map(|name| {
+                        match name.strip_prefix('@').and_then(|name| name.split_once('/')) {
+                            Some((scope, unscoped)) => vec![scope, unscoped],
+                            None => vec![*name],
+                        }
+                    })
+                    .collect();
+
+                let mut scoped = Scoped::new();
+                if opt.index {
+                    scoped = scoped.with_index(names.iter().copied(), opt.index_distance);
+                }
+                for name in opt.checks.iter().filter(|name| **name != Name::Scoped) {
+                    scoped = scoped.with_boxed_check(name.build(opt, &parts)?);
+                }
+                Box::new(scoped)
+            }
+            Name::SwappedCharacters => Box::new(SwappedCharacters),
+            Name::SwappedWords => {
+                Box::new(SwappedWords::new(&opt.delimiters).with_max_k(opt.max_k))
+            }
+            Name::Typos => Box::new(typos(opt)?),
+            Name::Version => Box::new(Version),
+        })
+    }
+}
+
+impl Name {
+    /// Instantiates the check as a generator, if it is one.
+    fn generator(&self, opt: &Opt) -> Result<Option<Box<dyn Generator>>, Error> {
+        Ok(Some(match self {
+            Name::Affixes => Box::new(Affixes::default()),
+            Name::Inflections => Box::new(Inflections::new(&opt.delimiters)),
+            Name::Leetspeak => Box::new(Leetspeak::default()),
+            Name::Omitted => Box::new(Omitted::new(&opt.alphabet)),
+            Name::Repeated => Box::new(Repeated),
+            Name::SwappedCharacters => Box::new(SwappedCharacters),
+            Name::SwappedWords => {
+                Box::new(SwappedWords::new(&opt.delimiters).with_max_k(opt.max_k))
+            }
+            Name::Typos => Box::new(typos(opt)?),
+            Name::Version => Box::new(Version),
+            _ => return Ok(None),
+        }))
+    }
+}
+
+/// Keyboard layouts that can be used to generate typo tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Layout {
+    Qwerty,
+    Qwertz,
+    Azerty,
+    Dvorak,
+}
+
+impl Layout {
+    fn typos(&self) -> Typos {
+        match self {
+            Layout::Qwerty => Typos::qwerty(),
+            Layout::Qwertz => Typos::qwertz(),
+            Layout::Azerty => Typos::azerty(),
+            Layout::Dvorak => Typos::dvorak(),
+        }
+    }
+}
+
+/// Phonetic algorithms that can be used by the phonetic check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Encoding {
+    DoubleMetaphone,
+    Soundex,
+}
+
+impl Encoding {
+    fn encoding(&self) -> PhoneticEncoding {
+        match self {
+            Encoding::DoubleMetaphone => PhoneticEncoding::DoubleMetaphone,
+            Encoding::Soundex => PhoneticEncoding::Soundex,
+        }
+    }
+}
+
+/// Builds the typo check from the table in the file given on the command line (or the default
+/// table if none was given), merged with the tables for any keyboard layouts given on the command
+/// line.
+fn typos(opt: &Opt) -> Result<Typos, Error> {
+    let typos = match &opt.typos {
+        Some(path) => Typos::new(read_typos(path)?.into_iter()),
+        None => Typos::default(),
+    };
+
+    Ok(opt
+        .layouts
+        .iter()
+        .fold(typos, |typos, layout| typos.merge(layout.typos())))
+}
+
+/// Loads a typo table from a file, which must contain a JSON object mapping single characters to
+/// arrays of replacements.
+fn read_typos(path: &PathBuf) -> Result<Vec<(char, Vec<String>)>, Error> {
+    let file = File::open(path).map_err(|e| Error::Io(path.clone(), e))?;
+    let table: HashMap<String, Vec<String>> =
+        serde_json::from_reader(BufReader::new(file)).map_err(|e| Error::Json(path.clone(), e))?;
+
+    table
+        .into_iter()
+        .map(|(key, typos)| {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok((c, typos)),
+                _ => Err(Error::InvalidTypoKey(path.clone(), key)),
+            }
+        })
+        .collect()
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("error reading {0}: {1}")]
+    Io(PathBuf, #[source] std::io::Error),
+
+    #[error("error parsing {0}: {1}")]
+    Json(PathBuf, #[source] serde_json::Error),
+
+    #[error("typo table {0} has key {1:?}, which is not a single character")]
+    InvalidTypoKey(PathBuf, String),
+}
@@ -0,0 +1,257 @@
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use clap::ValueEnum;
+use serde::Deserialize;
+use thiserror::Error;
+use typomania::{AuthorSet, Package};
+
+/// The format of an input file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Infer the format from the file extension.
+    Auto,
+
+    /// Comma separated values, with a header row.
+    Csv,
+
+    /// A JSON array of objects.
+    Json,
+
+    /// One JSON object per line.
+    Jsonl,
+}
+
+impl Format {
+    /// Resolves [`Format::Auto`] to a concrete format using the extension of `path`.
+    fn resolve(self, path: &Path) -> Result<Concrete, Error> {
+        Ok(match self {
+            Format::Auto => match path.extension().and_then(|ext| ext.to_str()) {
+                Some("csv") => Concrete::Csv,
+                Some("json") => Concrete::Json,
+                Some("jsonl" | "ndjson") => Concrete::Jsonl,
+                _ => return Err(Error::UnknownFormat(path.into())),
+            },
+            Format::Csv => Concrete::Csv,
+            Format::Json => Concrete::Json,
+            Format::Jsonl => Concrete::Jsonl,
+        })
+    }
+}
+
+/// A [`Format`] that has been resolved to a specific file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Concrete {
+    Csv,
+    Json,
+    Jsonl,
+}
+
+/// A single package read from an input file.
+///
+/// In CSV files, `authors` is a single field with authors delimited by semicolons; in JSON and JSON
+/// lines files, it may be either a delimited string or an array of strings.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Record {
+    pub name: String,
+
+    #[serde(default, deserialize_with = "deserialize_authors")]
+    pub authors: HashSet<String>,
+
+    #[serde(default)]
+    pub description: Option<String>,
+
+    #[serde(default)]
+    pub downloads: Option<u64>,
+
+    /// The creation time of the package, in seconds since the Unix epoch.
+    #[serde(default)]
+    pub created_at: Option<u64>,
+}
+
+impl AuthorSet for Record {
+    fn contains(&self, author: &str) -> bool {
+        self.authors.contains(author)
+    }
+}
+
+impl Package for Record {
+    fn authors(&self) -> &dyn AuthorSet {
+        self
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn shared_authors(&self, other: &dyn AuthorSet) -> bool {
+        self.authors.iter().any(|author| other.contains(author))
+    }
+
+    fn downloads(&self) -> Option<u64> {
+        self.downloads
+    }
+
+    fn created_at(&self) -> Option<SystemTime> {
+        self.created_at
+            .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+    }
+}
+
+/// Reads every record in the file at `path`.
+pub fn read(path: &Path, format: Format) -> Result<Vec<Record>, Error> {
+    let format = format.resolve(path)?;
+
+    let file = File::open(path).map_err(|e| Error::Io(path.into(), e))?;
+    match format {
+        Concrete::Csv => csv::Reader::from_reader(file)
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .map_err(|e| Error::Csv(path.into(), e)),
+        Concrete::Json => serde_json::from_reader(BufReader::new(file))
+            .map_err(|e| Error::JsonArray(path.into(), e)),
+        Concrete::Jsonl => {
+            let mut records = Vec::new();
+            for (i, line) in BufReader::new(file).lines().enumerate() {
+                let line = line.map_err(|e| Error::Io(path.into(), e))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+
+                records.push(
+                    serde_json::from_str(&line).map_err(|e| Error::Json(path.into(), i + 1, e))?,
+                );
+            }
+
+            Ok(records)
+        }
+    }
+}
+
+fn deserialize_authors<'de, D>(deserializer: D) -> Result<HashSet<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Authors {
+        List(Vec<String>),
+        Delimited(String),
+    }
+
+    Ok(match Option::<Authors>::deserialize(deserializer)? {
+        Some(Authors::List(authors)) => authors.into_iter().collect(),
+        Some(Authors::Delimited(authors)) => authors
+            .split(';')
+            .map(str::trim)
+            .filter(|author| !author.is_empty())
+            .map(String::from)
+            .collect(),
+        None => HashSet::new(),
+    })
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("cannot infer the format of {0}; use --format to specify it")]
+    UnknownFormat(PathBuf),
+
+    #[error("error reading {0}: {1}")]
+    Io(PathBuf, #[source] std::io::Error),
+
+    #[error("error parsing {0}: {1}")]
+    Csv(PathBuf, #[source] csv::Error),
+
+    #[error("error parsing {0} as a JSON array of packages: {1}")]
+    JsonArray(PathBuf, #[source] serde_json::Error),
+
+    #[error("error parsing {0} at line {1}: {2}")]
+    Json(PathBuf, usize, #[source] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records() -> Result<(), Box<dyn std::error::Error>> {
+        let csv: Vec<Record> = csv::Reader::from_reader(
+            "name,authors,downloads\nserde,dtolnay; erickt,100\nrand,,\n".as_bytes(),
+        )
+        .deserialize()
+        .collect::<Result<_, _>>()?;
+
+        assert_eq!(csv[0].name, "serde");
+        assert_eq!(
+            csv[0].authors,
+            HashSet::from(["dtolnay".into(), "erickt".into()])
+        );
+        assert_eq!(csv[0].downloads, Some(100));
+        assert_eq!(csv[1].name, "rand");
+        assert!(csv[1].authors.is_empty());
+        assert_eq!(csv[1].downloads, None);
+
+        let json: Record = serde_json::from_str(r#"{"name":"tokio","authors":["a","b"]}"#)?;
+        assert_eq!(json.authors, HashSet::from(["a".into(), "b".into()]));
+
+        let json: Record = serde_json::from_str(r#"{"name":"tokio","authors":"a;b"}"#)?;
+        assert_eq!(json.authors, HashSet::from(["a".into(), "b".into()]));
+
+        let json: Record = serde_json::from_str(r#"{"name":"tokio"}"#)?;
+        assert!(json.authors.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join(format!("typomania-input-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+
+        let names = |format: Format, file: &str, contents: &str| -> Result<Vec<String>, Error> {
+            let path = dir.join(file);
+            std::fs::write(&path, contents).map_err(|e| Error::Io(path.clone(), e))?;
+            Ok(read(&path, format)?
+                .into_iter()
+                .map(|record| record.name)
+                .collect())
+        };
+
+        let want = vec![String::from("serde"), String::from("tokio")];
+        assert_eq!(names(Format::Auto, "a.csv", "name\nserde\ntokio\n")?, want);
+        assert_eq!(
+            names(
+                Format::Auto,
+                "a.json",
+                r#"[{"name": "serde"}, {"name": "tokio"}]"#
+            )?,
+            want
+        );
+        assert_eq!(
+            names(
+                Format::Auto,
+                "a.jsonl",
+                "{\"name\": \"serde\"}\n\n{\"name\": \"tokio\"}\n"
+            )?,
+            want
+        );
+        assert_eq!(names(Format::Csv, "a.txt", "name\nserde\ntokio\n")?, want);
+
+        assert!(matches!(
+            names(Format::Auto, "a.txt", ""),
+            Err(Error::UnknownFormat(_))
+        ));
+        assert!(matches!(
+            names(Format::Auto, "b.json", r#"{"name": "serde"}"#),
+            Err(Error::JsonArray(..))
+        ));
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,335 @@
+//! A command line interface to check a list of packages against a corpus of popular packages, or
+//! to audit a corpus for packages that squat other packages in the same corpus.
+//!
+//! Packages are read from CSV, JSON, or JSON lines files: see [`input::Record`] for the fields that
+//! are understood. Only `name` is required.
+//!
+//! The process exits with status 0 if no potential squats were found, 1 if one or more potential
+//! squats were found, and 2 if an error occurred.
+
+use std::{collections::HashMap, io::Write, path::PathBuf, process::ExitCode};
+
+use clap::{Parser, ValueEnum};
+use thiserror::Error;
+use typomania::{
+    corpus::{IndexedCorpus, Normalized},
+    normalize::{CratesIo, Npm, Pypi},
+    Corpus, Harness, HarnessError, HarnessReport, Package,
+};
+
+mod checks;
+mod input;
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Opt {
+    /// File containing the corpus of popular packages to check against
+    #[arg(short, long, value_name = "FILE")]
+    corpus: PathBuf,
+
+    /// File containing the packages to check
+    #[arg(
+        value_name = "FILE",
+        required_unless_present = "audit",
+        conflicts_with = "audit"
+    )]
+    packages: Option<PathBuf>,
+
+    /// Check the packages in the corpus against each other, instead of checking a separate file
+    /// of packages
+    #[arg(long)]
+    audit: bool,
+
+    /// Minimum downloads for a corpus package to be considered a target when auditing
+    #[arg(long, requires = "audit")]
+    min_downloads: Option<u64>,
+
+    /// Format of the input files
+    #[arg(long, value_enum, default_value_t = input::Format::Auto)]
+    format: input::Format,
+
+    /// Checks to run, delimited by commas
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "bitflips,omitted,repeated,swapped-characters,swapped-words,typos,version"
+    )]
+    checks: Vec<checks::Name>,
+
+    /// Valid characters in package names
+    #[arg(
+        long,
+        default_value = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz1234567890-_"
+    )]
+    pub(crate) alphabet: String,
+
+    /// JSON file mapping characters to their common typos, replacing the default table
+    #[arg(long, value_name = "FILE")]
+    pub(crate) typos: Option<PathBuf>,
+
+    /// Keyboard layouts to add typos for, delimited by commas
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub(crate) layouts: Vec<checks::Layout>,
+
+    /// Phonetic algorithm used by the phonetic check
+    #[arg(long, value_enum, default_value = "double-metaphone")]
+    pub(crate) phonetic: checks::Encoding,
+
+    /// Characters that delimit words in package names
+    #[arg(long, default_value = "-_.")]
+    pub(crate) delimiters: String,
+
+    /// Maximum number of words to permute when checking for swapped words
+    #[arg(long, default_value_t = 5)]
+    pub(crate) max_k: usize,
+
+    /// Maximum edit distance when checking edit distance
+    #[arg(long, default_value_t = 2)]
+    pub(crate) max_distance: usize,
+
+    /// Maximum number of edits to chain together when checking chained edits
+    #[arg(long, default_value_t = 2)]
+    pub(crate) chain_depth: usize,
+
+    /// Minimum name length when checking edit distance
+    #[arg(long, default_value_t = 4)]
+    pub(crate) min_length: usize,
+
+    /// Normalize package names using the rules of the given registry
+    #[arg(long, value_enum, value_name = "REGISTRY")]
+    normalize: Option<Registry>,
+
+    /// Index the corpus, which is faster when checking many packages against a large corpus
+    #[arg(long)]
+    index: bool,
+
+    /// Maximum edit distance of lookups in the corpus index
+    #[arg(long, default_value_t = 1, requires = "index")]
+    index_distance: usize,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Output::Human)]
+    output: Output,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Output {
+    /// One line per potential squat.
+    Human,
+
+    /// A single JSON document.
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Registry {
+    CratesIo,
+    Pypi,
+    Npm,
+}
+
+impl Registry {
+    fn normalize<C>(&self, corpus: C, names: &[String]) -> Normalized<C>
+    where
+        C: Corpus,
+    {
+        let names = names.iter().map(String::as_str);
+        match self {
+            Registry::CratesIo => Normalized::new(corpus, names, CratesIo),
+            Registry::Pypi => Normalized::new(corpus, names, Pypi),
+            Registry::Npm => Normalized::new(corpus, names, Npm),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let opt = Opt::parse();
+
+    match run(&opt) {
+        Ok(report) if report.packages.is_empty() => ExitCode::SUCCESS,
+        Ok(_) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(opt: &Opt) -> Result<HarnessReport, Error> {
+    let report = report(opt)?;
+
+    let mut stdout = std::io::stdout().lock();
+    match opt.output {
+        Output::Human => {
+            for (name, squats) in report.packages.iter() {
+                for squat in squats.iter() {
+                    writeln!(stdout, "{name}: {squat}")?;
+                }
+            }
+        }
+        Output::Json => {
+            serde_json::to_writer_pretty(&mut stdout, &report)?;
+            writeln!(stdout)?;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Checks the packages, or audits the corpus, as configured by `opt`.
+fn report(opt: &Opt) -> Result<HarnessReport, Error> {
+    let corpus: HashMap<String, input::Record> = input::read(&opt.corpus, opt.format)?
+        .into_iter()
+        .map(|record| (record.name.clone(), record))
+        .collect();
+    let packages = match &opt.packages {
+        Some(path) => Some(input::read(path, opt.format)?),
+        None => None,
+    };
+
+    let names: Vec<String> = corpus.keys().cloned().collect();
+    match opt.normalize {
+        Some(registry) => {
+            let normalized = registry.normalize(corpus, &names);
+            let names = Names {
+                checked: normalized.names().map(String::from).collect(),
+                corpus: names,
+            };
+            index_and_check(opt, &names, normalized, packages)
+        }
+        None => {
+            let names = Names {
+                checked: names.clone(),
+                corpus: names,
+            };
+            index_and_check(opt, &names, corpus, packages)
+        }
+    }
+}
+
+/// The names of the packages in the corpus.
+struct Names {
+    /// The names as they are spelled in the corpus.
+    corpus: Vec<String>,
+
+    /// The names that checks and the index see, which are normalized if a registry was given.
+    checked: Vec<String>,
+}
+
+fn index_and_check<C>(
+    opt: &Opt,
+    names: &Names,
+    corpus: C,
+    packages: Option<Vec<input::Record>>,
+) -> Result<HarnessReport, Error>
+where
+    C: Corpus + Send + Sync + 'static,
+{
+    if opt.index {
+        let indexed = IndexedCorpus::new(
+            corpus,
+            names.checked.iter().map(String::as_str),
+            opt.index_distance,
+        );
+        check(opt, names, indexed, packages)
+    } else {
+        check(opt, names, corpus, packages)
+    }
+}
+
+fn check<C>(
+    opt: &Opt,
+    names: &Names,
+    corpus: C,
+    packages: Option<Vec<input::Record>>,
+) -> Result<HarnessReport, Error>
+where
+    C: Corpus + Send + Sync + 'static,
+{
+    let checked: Vec<&str> = names.checked.iter().map(String::as_str).collect();
+
+    let mut builder = Harness::empty_builder();
+    for name in opt.checks.iter() {
+        builder = builder.with_boxed_check(name.build(opt, &checked)?);
+    }
+    let harness = builder.build(corpus);
+
+    Ok(match packages {
+        Some(packages) => harness.check(packages.into_iter().map(|record| {
+            let name = record.name.clone();
+            let package: Box<dyn Package> = Box::new(record);
+            (name, package)
+        }))?,
+        // Audit results are keyed by the names given here, which have to match the spelling of
+        // the squatted packages for each pair to be reported once.
+        None => harness.audit_corpus(names.corpus.iter().map(String::as_str), opt.min_downloads)?,
+    }
+    .into())
+}
+
+#[derive(Error, Debug)]
+enum Error {
+    #[error(transparent)]
+    Input(#[from] input::Error),
+
+    #[error(transparent)]
+    Check(#[from] checks::Error),
+
+    #[error(transparent)]
+    Harness(#[from] HarnessError),
+
+    #[error("error writing output: {0}")]
+    Output(#[from] std::io::Error),
+
+    #[error("error writing output: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_normalized() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir().join(format!("typomania-main-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let corpus = dir.join("corpus.csv");
+        std::fs::write(&corpus, "name,downloads\nSerde_Json,1000\nserde_jsno,10\n")?;
+
+        for index in [false, true] {
+            let mut args = vec![
+                "typomania",
+                "--corpus",
+                corpus.to_str().unwrap(),
+                "--audit",
+                "--normalize",
+                "crates-io",
+                "--checks",
+                "swapped-characters",
+            ];
+            if index {
+                args.push("--index");
+            }
+
+            // Only the squat of the more popular package is reported, keyed by the corpus
+            // spelling.
+            let report = report(&Opt::try_parse_from(args)?)?;
+            let have: Vec<(String, Vec<String>)> = report
+                .packages
+                .into_iter()
+                .map(|(name, squats)| (name, squats.iter().map(|s| s.to_string()).collect()))
+                .collect();
+            assert_eq!(
+                have,
+                vec![(
+                    String::from("serde_jsno"),
+                    vec![String::from("swaps characters in Serde_Json")]
+                )]
+            );
+        }
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+use super::{check_generated, Check, Corpus, Details, Generator, Package, Squat};
+
+/// Checks whether a package only differs from a package in the corpus by adding or removing a
+/// prefix or suffix.
+///
+/// This attempts to detect _combosquatting_, where an ecosystem or language specific word is added
+/// to the name of a popular package (for example, `rust-serde` or `requests-py`), or removed from
+/// the name of a popular package that already includes one.
+///
+/// Unlike [`Version`][super::Version], which only strips trailing version numbers, the affixes
+/// are configurable. Affixes are matched literally, so any delimiter must be included in the affix
+/// itself.
+pub struct Affixes {
+    prefixes: Vec<String>,
+    suffixes: Vec<String>,
+}
+
+impl Affixes {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "affixes";
+
+    /// Instantiates an affix check with the given prefixes and suffixes.
+    ///
+    /// For example, with a prefix of `rust-`, a package `rust-foo` will be checked against `foo`,
+    /// and a package `foo` will be checked against `rust-foo`.
+    pub fn new<'a>(
+        prefixes: impl Iterator<Item = &'a str>,
+        suffixes: impl Iterator<Item = &'a str>,
+    ) -> Self {
+        Self {
+            prefixes: prefixes.map(String::from).collect(),
+            suffixes: suffixes.map(String::from).collect(),
+        }
+    }
+}
+
+impl Default for Affixes {
+    /// Instantiates an affix check with a list of affixes commonly used in combosquatting attacks
+    /// on crates.io, npm, and PyPI.
+    fn default() -> Self {
+        Self::new(PREFIXES.iter().copied(), SUFFIXES.iter().copied())
+    }
+}
+
+impl Check for Affixes {
+    fn check(
+        &self,
+        corpus: &dyn Corpus,
+        name: &str,
+        package: &dyn Package,
+    ) -> crate::Result<Vec<Squat>> {
+        check_generated(self, corpus, name, package)
+    }
+
+    fn squats_of(&self, package: &str) -> Option<Vec<(String, Squat)>> {
+        // Adding and removing affixes are inverses of each other.
+        let squats = self
+            .generate(package)
+            .into_iter()
+            .map(|squat| {
+                let name = String::from(squat.package());
+                let squat = Squat::Affix(Details::new(&name, package));
+                (name, squat)
+            })
+            .collect();
+
+        Some(squats)
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Affixes"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that add or remove a common prefix or suffix."
+    }
+}
+
+impl Generator for Affixes {
+    fn generate(&self, name: &str) -> Vec<Squat> {
+        let mut squats: Vec<Squat> = Vec::new();
+
+        if name.is_empty() {
+            return squats;
+        }
+
+        let prefixed = self.prefixes.iter().flat_map(|prefix| {
+            [
+                name.strip_prefix(prefix.as_str()).map(String::from),
+                Some(format!("{prefix}{name}")),
+            ]
+        });
+        let suffixed = self.suffixes.iter().flat_map(|suffix| {
+            [
+                name.strip_suffix(suffix.as_str()).map(String::from),
+                Some(format!("{name}{suffix}")),
+            ]
+        });
+
+        for name_to_check in prefixed.chain(suffixed).flatten() {
+            if !name_to_check.is_empty()
+                && !squats.iter().any(|squat| squat.package() == name_to_check)
+            {
+                squats.push(Squat::Affix(Details::new(name, &name_to_check)));
+            }
+        }
+
+        squats
+    }
+}
+
+static PREFIXES: &[&str] = &[
+    "go-", "js-", "lib", "node-", "py-", "python-", "rs-", "rust-", "rust_",
+];
+
+static SUFFIXES: &[&str] = &[
+    "-api", "-cli", "-core", "-dev", "-go", "-js", ".js", "-lib", "_lib", "-node", "-py", "_py",
+    "-python", "-rs", "_rs", "-rust", "-sdk", "-util", "-utils", "_utils", "2", "3", "js",
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::checks::testutil::{assert_check, assert_squats_of, check_squats};
+
+    use super::*;
+
+    #[test]
+    fn test_affixes() -> crate::Result<()> {
+        #[track_caller]
+        fn test(input: &str, want: &[&str]) -> crate::Result<()> {
+            assert_check(
+                Affixes::new(["rust-", "py"].into_iter(), ["-rs", "2"].into_iter()),
+                input,
+                want,
+            )
+        }
+
+        test("", &[])?;
+        test("serde", &["rust-serde", "pyserde", "serde-rs", "serde2"])?;
+        test(
+            "rust-serde",
+            &[
+                "serde",
+                "rust-rust-serde",
+                "pyrust-serde",
+                "rust-serde-rs",
+                "rust-serde2",
+            ],
+        )?;
+        test(
+            "tokio-core2",
+            &[
+                "tokio-core",
+                "rust-tokio-core2",
+                "pytokio-core2",
+                "tokio-core2-rs",
+                "tokio-core22",
+            ],
+        )?;
+        test("rust-", &["rust-rust-", "pyrust-", "rust--rs", "rust-2"])?;
+        test(
+            "日本-rs",
+            &[
+                "日本",
+                "rust-日本-rs",
+                "py日本-rs",
+                "日本-rs-rs",
+                "日本-rs2",
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_affixes() -> crate::Result<()> {
+        #[track_caller]
+        fn test(corpus: &[&str], input: &str, want: &[&str]) -> crate::Result<()> {
+            let squats = check_squats(Affixes::default(), corpus, input)?;
+            assert_eq!(
+                squats.iter().map(Squat::package).collect::<Vec<_>>(),
+                want.to_vec()
+            );
+            Ok(())
+        }
+
+        let corpus = [
+            "serde",
+            "requests",
+            "lodash",
+            "tokio-core",
+            "python-requests",
+        ];
+
+        test(&corpus, "serde-rs", &["serde"])?;
+        test(&corpus, "rust-serde", &["serde"])?;
+        test(&corpus, "requests-py", &["requests"])?;
+        test(&corpus, "python-requests", &["requests"])?;
+        test(&corpus, "requests", &["python-requests"])?;
+        test(&corpus, "node-lodash", &["lodash"])?;
+        test(&corpus, "lodash-utils", &["lodash"])?;
+        test(&corpus, "tokio-core2", &["tokio-core"])?;
+        test(&corpus, "serde", &[])?;
+        test(&corpus, "tokio", &["tokio-core"])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_affixes_squats_of() -> crate::Result<()> {
+        let affixes = || Affixes::new(["rust-"].into_iter(), ["-rs"].into_iter());
+
+        assert_squats_of(affixes(), "serde", &["rust-serde", "serde-rs"])?;
+        assert_squats_of(
+            affixes(),
+            "rust-serde",
+            &["serde", "rust-rust-serde", "rust-serde-rs"],
+        )
+    }
+}
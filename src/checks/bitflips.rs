@@ -5,7 +5,7 @@ use tracing::instrument;
 
 use crate::{BoxError, Corpus, Package};
 
-use super::{Check, Squat};
+use super::{Check, Details, FlippedBit, Squat};
 
 /// Checks whether the package is a bitflipped version of a package in the corpus.
 ///
@@ -13,11 +13,15 @@ use super::{Check, Squat};
 ///
 /// [bitsquatting]: https://en.wikipedia.org/wiki/Bitsquatting
 pub struct Bitflips {
+    alphabet: BTreeSet<char>,
     bitflips: BTreeMap<String, Vec<usize>>,
     names: Vec<String>,
 }
 
 impl Bitflips {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "bitflips";
+
     /// Instantiates a bitflip check.
     ///
     /// `alphabet` is the list of characters that are valid in a package name.
@@ -34,13 +38,14 @@ impl Bitflips {
         for (i, name) in names.enumerate() {
             cloned_names.push(name.into());
             for bitflipped_name in
-                bitflip::ascii_str(name).filter(|bf| bf.chars().all(|c| alphabet.contains(&c)))
+                bitflip::utf8(name).filter(|bf| bf.chars().all(|c| alphabet.contains(&c)))
             {
                 bitflips.entry(bitflipped_name).or_default().push(i);
             }
         }
 
         Self {
+            alphabet,
             bitflips,
             names: cloned_names,
         }
@@ -63,13 +68,51 @@ impl Check for Bitflips {
                     len: self.names.len(),
                 })?;
                 if corpus.possible_squat(name_to_check, name, package)? {
-                    squats.push(Squat::Bitflip(name_to_check.into()))
+                    let flip = FlippedBit::between(name, name_to_check).ok_or_else(|| {
+                        Error::NotBitflip {
+                            name: name.into(),
+                            package: name_to_check.clone(),
+                        }
+                    })?;
+
+                    squats.push(Squat::Bitflip {
+                        details: Details::new(name, name_to_check),
+                        flip,
+                    })
                 }
             }
         }
 
         Ok(squats)
     }
+
+    fn squats_of(&self, package: &str) -> Option<Vec<(String, Squat)>> {
+        let squats = bitflip::utf8(package)
+            .filter(|bf| bf.chars().all(|c| self.alphabet.contains(&c)))
+            .filter_map(|name| {
+                let flip = FlippedBit::between(&name, package)?;
+                let squat = Squat::Bitflip {
+                    details: Details::new(&name, package),
+                    flip,
+                };
+                Some((name, squat))
+            })
+            .collect();
+
+        Some(squats)
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Bitflips"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that differ by a single flipped bit."
+    }
 }
 
 #[derive(Error, Debug)]
@@ -79,11 +122,17 @@ enum Error {
 
     #[error("unexpected out of range index {index} in vec of length {len}")]
     OutOfRangeIndex { index: usize, len: usize },
+
+    #[error("{name} is unexpectedly not a single bitflip of {package}")]
+    NotBitflip { name: String, package: String },
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::checks::testutil::assert_check;
+    use crate::checks::{
+        testutil::{assert_check, assert_squats_of, check_squats},
+        Edit,
+    };
 
     use super::*;
 
@@ -91,7 +140,43 @@ mod tests {
     fn test_bitflips() -> crate::Result<()> {
         assert_check(Bitflips::new("abcdef", ["ab"].into_iter()), "ac", &["ab"])?;
 
+        // Non-ASCII names can also be bitflipped: U+00E9 is 0xC3 0xA9 in UTF-8, and U+00E8 is 0xC3
+        // 0xA8.
+        assert_check(Bitflips::new("éèa", ["éa"].into_iter()), "èa", &["éa"])?;
+
         // Even more limited alphabet.
         assert_check(Bitflips::new("ab", ["ab"].into_iter()), "ac", &[])
     }
+
+    #[test]
+    fn test_bitflip_details() -> crate::Result<()> {
+        let squats = check_squats(Bitflips::new("abcdef", ["ab"].into_iter()), &["ab"], "ac")?;
+
+        assert_eq!(
+            squats,
+            vec![Squat::Bitflip {
+                details: Details {
+                    package: "ab".into(),
+                    edit: Some(Edit {
+                        bytes: 1..2,
+                        chars: 1..2,
+                        original: "c".into(),
+                        replacement: "b".into(),
+                    }),
+                },
+                flip: FlippedBit { byte: 1, bit: 0 },
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bitflips_squats_of() -> crate::Result<()> {
+        assert_squats_of(
+            Bitflips::new("abcdef", ["ab"].into_iter()),
+            "ab",
+            &["cb", "eb", "ac", "af"],
+        )
+    }
 }
@@ -0,0 +1,232 @@
+use std::collections::HashSet;
+
+use crate::{Corpus, Package};
+
+use super::{check_generated, Check, Details, Generator, Squat};
+
+/// Checks whether a package name is reached from a package in the corpus by applying several
+/// generators in turn: for example, [`Repeated`][super::Repeated] followed by
+/// [`SwappedCharacters`][super::SwappedCharacters], or [`Typos`][super::Typos] applied twice.
+///
+/// Every configured generator is applied to the package name, then to each candidate it produces,
+/// and so on, up to the maximum depth. Each candidate name is only reported once, through the
+/// shortest chain of edits that produces it, and the package name itself is never a candidate.
+///
+/// Candidates produced by a single generator are reported as that generator would report them,
+/// so a chain with a maximum depth of 1 is equivalent to running each generator as a separate
+/// check. Longer chains are reported as [`Squat::Chain`], with one step per generator applied.
+///
+/// The number of candidates grows exponentially with the depth, so generators that produce many
+/// candidates, such as [`Omitted`][super::Omitted] with a large alphabet, should be chained with
+/// care.
+pub struct Chain {
+    generators: Vec<Box<dyn Generator>>,
+    max_depth: usize,
+}
+
+impl Chain {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "chain";
+
+    /// Instantiates a chain with no generators and a maximum depth of 2.
+    pub fn new() -> Self {
+        Self {
+            generators: Vec::new(),
+            max_depth: 2,
+        }
+    }
+
+    /// Adds a generator to the chain.
+    pub fn with_generator<G>(mut self, generator: G) -> Self
+    where
+        G: Generator + 'static,
+    {
+        self.generators.push(Box::new(generator));
+        self
+    }
+
+    /// Adds a boxed generator to the chain.
+    pub fn with_boxed_generator(mut self, generator: Box<dyn Generator>) -> Self {
+        self.generators.push(generator);
+        self
+    }
+
+    /// Changes the maximum number of generators applied in a single chain.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+impl Default for Chain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Generator for Chain {
+    fn generate(&self, name: &str) -> Vec<Squat> {
+        let mut squats = Vec::new();
+        let mut seen: HashSet<String> = [String::from(name)].into_iter().collect();
+        let mut frontier: Vec<(String, Vec<Squat>)> = vec![(String::from(name), Vec::new())];
+
+        for _ in 0..self.max_depth {
+            let mut next = Vec::new();
+
+            for (current, steps) in frontier.into_iter() {
+                for generator in self.generators.iter() {
+                    for step in generator.generate(&current) {
+                        let candidate = step.package();
+                        if !seen.insert(String::from(candidate)) {
+                            continue;
+                        }
+
+                        let candidate = String::from(candidate);
+                        let mut steps = steps.clone();
+                        steps.push(step);
+
+                        squats.push(if steps.len() == 1 {
+                            steps[0].clone()
+                        } else {
+                            Squat::Chain {
+                                details: Details::new(name, &candidate),
+                                steps: steps.clone(),
+                            }
+                        });
+                        next.push((candidate, steps));
+                    }
+                }
+            }
+
+            frontier = next;
+        }
+
+        squats
+    }
+}
+
+impl Check for Chain {
+    fn check(
+        &self,
+        corpus: &dyn Corpus,
+        name: &str,
+        package: &dyn Package,
+    ) -> crate::Result<Vec<Squat>> {
+        check_generated(self, corpus, name, package)
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Chained edits"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that combine several edits made by other checks."
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checks::{
+        testutil::{assert_check, check_squats},
+        Omitted, Repeated, SwappedCharacters, Typos,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_chain() -> crate::Result<()> {
+        let chain = Chain::new()
+            .with_generator(Repeated)
+            .with_generator(SwappedCharacters);
+
+        let squats = check_squats(chain, &["serde", "sedre"], "seddre")?;
+        assert_eq!(squats.len(), 2);
+
+        // A single step is reported as is.
+        assert_eq!(
+            squats[0],
+            Squat::RepeatedCharacter(Details::new("seddre", "sedre"))
+        );
+
+        // Two steps are reported as a chain.
+        let Squat::Chain { details, steps } = &squats[1] else {
+            panic!("unexpected squat {:?}", squats[1]);
+        };
+        assert_eq!(details, &Details::new("seddre", "serde"));
+        assert_eq!(
+            steps,
+            &[
+                Squat::RepeatedCharacter(Details::new("seddre", "sedre")),
+                Squat::SwappedCharacters(Details::new("sedre", "serde")),
+            ]
+        );
+        assert_eq!(squats[1].check(), "chain");
+        assert_eq!(
+            squats[1].to_string(),
+            "is 2 edits away from serde: repeats characters in sedre, then swaps characters in \
+             serde"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_chain_depth() -> crate::Result<()> {
+        let typos = || {
+            Typos::new(
+                [
+                    ('a', vec![String::from("b")]),
+                    ('b', vec![String::from("c")]),
+                ]
+                .into_iter(),
+            )
+        };
+
+        assert_check(
+            Chain::new().with_generator(typos()).with_max_depth(0),
+            "aa",
+            &[],
+        )?;
+        assert_check(
+            Chain::new().with_generator(typos()).with_max_depth(1),
+            "aa",
+            &["ba", "ab"],
+        )?;
+        assert_check(
+            Chain::new().with_generator(typos()),
+            "aa",
+            &["ba", "ab", "ca", "bb", "ac"],
+        )?;
+        assert_check(
+            Chain::new().with_generator(typos()).with_max_depth(3),
+            "aa",
+            &["ba", "ab", "ca", "bb", "ac", "cb", "bc"],
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_chain_dedup() {
+        // Both orders of omission produce the same candidates, which are only reported once, and
+        // never as the original name.
+        let chain = Chain::new()
+            .with_generator(Omitted::new("ab"))
+            .with_generator(Repeated);
+
+        let candidates: Vec<String> = chain
+            .generate("a")
+            .iter()
+            .map(|squat| String::from(squat.package()))
+            .collect();
+        let unique: HashSet<&String> = candidates.iter().collect();
+
+        assert_eq!(candidates.len(), unique.len());
+        assert!(!unique.contains(&String::from("a")));
+        assert!(unique.contains(&String::from("aab")));
+    }
+}
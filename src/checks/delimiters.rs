@@ -0,0 +1,199 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::{BoxError, Corpus, Package};
+
+use super::{Check, Details, Squat};
+
+/// Checks whether a package only differs from a package in the corpus by inserting, removing, or
+/// changing word delimiters.
+///
+/// For example, with delimiters `-_.`, the names `foobar`, `foo-bar`, `foo_bar`, `foo.bar`, and
+/// `foo-b_ar` are all considered to be potential squats of each other.
+pub struct Delimiters {
+    delimiters: Vec<char>,
+    stripped: BTreeMap<String, Vec<usize>>,
+    names: Vec<String>,
+}
+
+impl Delimiters {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "delimiters";
+
+    /// Instantiates a delimiter check, using each character in `delimiters` as a possible word
+    /// delimiter.
+    ///
+    /// `names` is generally the same set of names that exist in the top package corpus: a local
+    /// copy is required so that the names can be indexed without their delimiters during
+    /// instantiation, rather than having to compare the package against every name in the corpus
+    /// each time the check is run.
+    #[instrument(level = "TRACE", skip(names))]
+    pub fn new<'a>(delimiters: &str, names: impl Iterator<Item = &'a str>) -> Self {
+        let delimiters: Vec<char> = delimiters.chars().collect();
+        let mut stripped: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        let mut cloned_names = Vec::new();
+
+        for (i, name) in names.enumerate() {
+            cloned_names.push(name.into());
+            stripped
+                .entry(strip(name, &delimiters))
+                .or_default()
+                .push(i);
+        }
+
+        Self {
+            delimiters,
+            stripped,
+            names: cloned_names,
+        }
+    }
+}
+
+impl Check for Delimiters {
+    fn check(
+        &self,
+        corpus: &dyn Corpus,
+        name: &str,
+        package: &dyn Package,
+    ) -> crate::Result<Vec<Squat>> {
+        let mut squats = Vec::new();
+
+        let stripped = strip(name, &self.delimiters);
+        if stripped.is_empty() {
+            return Ok(squats);
+        }
+
+        if let Some(indices) = self.stripped.get(&stripped) {
+            for index in indices.iter().copied() {
+                let name_to_check = self.names.get(index).ok_or(Error::OutOfRangeIndex {
+                    index,
+                    len: self.names.len(),
+                })?;
+                if corpus.possible_squat(name_to_check, name, package)? {
+                    squats.push(Squat::Delimiter(Details::new(name, name_to_check)));
+                }
+            }
+        }
+
+        Ok(squats)
+    }
+
+    /// Only names that insert, remove, or change a single delimiter between two characters of the
+    /// package are returned.
+    fn squats_of(&self, package: &str) -> Option<Vec<(String, Squat)>> {
+        let stripped = strip(package, &self.delimiters);
+        if stripped.is_empty() {
+            return Some(Vec::new());
+        }
+
+        let chars: Vec<char> = package.chars().collect();
+        let mut candidates = BTreeSet::new();
+        for i in 0..chars.len() {
+            let rest: String = chars[i + 1..].iter().collect();
+            let before: String = chars[..i].iter().collect();
+            if self.delimiters.contains(&chars[i]) {
+                candidates.insert(format!("{before}{rest}"));
+                for delimiter in self.delimiters.iter() {
+                    candidates.insert(format!("{before}{delimiter}{rest}"));
+                }
+            }
+            if i > 0 {
+                for delimiter in self.delimiters.iter() {
+                    candidates.insert(format!("{before}{delimiter}{}{rest}", chars[i]));
+                }
+            }
+        }
+
+        Some(
+            candidates
+                .into_iter()
+                .filter(|candidate| {
+                    candidate != package && strip(candidate, &self.delimiters) == stripped
+                })
+                .map(|candidate| {
+                    let squat = Squat::Delimiter(Details::new(&candidate, package));
+                    (candidate, squat)
+                })
+                .collect(),
+        )
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Delimiters"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that insert, remove, or change delimiters between words."
+    }
+}
+
+fn strip(name: &str, delimiters: &[char]) -> String {
+    name.chars().filter(|c| !delimiters.contains(c)).collect()
+}
+
+#[derive(Error, Debug)]
+enum Error {
+    #[error(transparent)]
+    Corpus(#[from] BoxError),
+
+    #[error("unexpected out of range index {index} in vec of length {len}")]
+    OutOfRangeIndex { index: usize, len: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checks::testutil::{assert_check, assert_squats_of};
+
+    use super::*;
+
+    #[test]
+    fn test_delimiters() -> crate::Result<()> {
+        #[track_caller]
+        fn test(corpus: &[&str], input: &str, want: &[&str]) -> crate::Result<()> {
+            assert_check(Delimiters::new("-_.", corpus.iter().copied()), input, want)
+        }
+
+        let corpus = ["foobar", "foo-bar-baz", "serde_json", "日本-語", "-"];
+
+        test(&corpus, "", &[])?;
+        test(&corpus, "-", &[])?;
+        test(&corpus, "foobar", &[])?;
+        test(&corpus, "foo-bar", &["foobar"])?;
+        test(&corpus, "foo.bar", &["foobar"])?;
+        test(&corpus, "f-o-o-b-a-r", &["foobar"])?;
+        test(&corpus, "foobarbaz", &["foo-bar-baz"])?;
+        test(&corpus, "foo_bar-baz", &["foo-bar-baz"])?;
+        test(&corpus, "serde-json", &["serde_json"])?;
+        test(&corpus, "serdejson", &["serde_json"])?;
+        test(&corpus, "serde_jsno", &[])?;
+        test(&corpus, "日本語", &["日本-語"])?;
+
+        // Only the given delimiters are considered.
+        assert_check(Delimiters::new("-", corpus.iter().copied()), "foo.bar", &[])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_delimiters_squats_of() -> crate::Result<()> {
+        #[track_caller]
+        fn test(package: &str, want: &[&str]) -> crate::Result<()> {
+            assert_squats_of(Delimiters::new("-_", [package].into_iter()), package, want)
+        }
+
+        test("", &[])?;
+        test("-", &[])?;
+        test("ab", &["a-b", "a_b"])?;
+        test(
+            "a-bc",
+            &["abc", "a_bc", "a--bc", "a-_bc", "a_-bc", "a-b-c", "a-b_c"],
+        )?;
+        test("日本", &["日-本", "日_本"])
+    }
+}
@@ -0,0 +1,184 @@
+use std::ops::Range;
+
+/// Structured evidence for a potential typosquat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Details {
+    /// The name of the package in the corpus that may be squatted.
+    pub package: String,
+
+    /// The part of the checked package name that differs from the corpus package name, if known.
+    pub edit: Option<Edit>,
+}
+
+impl Details {
+    /// Instantiates details for a potential squat of `package` by the package named `name`,
+    /// calculating the edit between the two names.
+    pub fn new(name: &str, package: &str) -> Self {
+        Self {
+            package: package.into(),
+            edit: Edit::between(name, package),
+        }
+    }
+
+    /// Instantiates details for a potential squat of `package` without any edit information.
+    pub fn for_package(package: &str) -> Self {
+        Self {
+            package: package.into(),
+            edit: None,
+        }
+    }
+}
+
+/// The span of a checked package name that differs from a package in the corpus.
+///
+/// Replacing `original` at the given span of the checked package name with `replacement` results
+/// in the name of the package in the corpus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edit {
+    /// The byte range within the checked package name that differs.
+    pub bytes: Range<usize>,
+
+    /// The character range within the checked package name that differs.
+    pub chars: Range<usize>,
+
+    /// The differing substring of the checked package name.
+    pub original: String,
+
+    /// The substring of the corpus package name that takes the place of `original`.
+    pub replacement: String,
+}
+
+impl Edit {
+    /// Calculates the smallest single span of `name` that must be replaced to produce `package`,
+    /// or `None` if the names are identical.
+    ///
+    /// Note that this is a single contiguous span: for names that differ in several places, the
+    /// span will cover everything from the first difference to the last.
+    pub fn between(name: &str, package: &str) -> Option<Self> {
+        if name == package {
+            return None;
+        }
+
+        let name_chars: Vec<(usize, char)> = name.char_indices().collect();
+        let package_chars: Vec<char> = package.chars().collect();
+
+        let prefix = name_chars
+            .iter()
+            .zip(package_chars.iter())
+            .take_while(|((_, a), b)| a == *b)
+            .count();
+        let suffix = name_chars[prefix..]
+            .iter()
+            .rev()
+            .zip(package_chars[prefix..].iter().rev())
+            .take_while(|((_, a), b)| a == *b)
+            .count();
+
+        let chars = prefix..name_chars.len() - suffix;
+        let byte_offset = |i: usize| {
+            name_chars
+                .get(i)
+                .map(|(offset, _)| *offset)
+                .unwrap_or(name.len())
+        };
+        let bytes = byte_offset(chars.start)..byte_offset(chars.end);
+
+        Some(Self {
+            original: name[bytes.clone()].into(),
+            replacement: package_chars[prefix..package_chars.len() - suffix]
+                .iter()
+                .collect(),
+            bytes,
+            chars,
+        })
+    }
+}
+
+/// The bit that was flipped to produce a bitflipped package name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlippedBit {
+    /// The byte offset within the checked package name.
+    pub byte: usize,
+
+    /// The bit within the byte, where 0 is the least significant bit.
+    pub bit: u8,
+}
+
+impl FlippedBit {
+    /// Finds the bit that must be flipped in `name` to produce `package`, if they differ by
+    /// exactly one bit.
+    pub fn between(name: &str, package: &str) -> Option<Self> {
+        if name.len() != package.len() {
+            return None;
+        }
+
+        let mut differing = name
+            .bytes()
+            .zip(package.bytes())
+            .enumerate()
+            .filter(|(_, (a, b))| a != b);
+
+        match (differing.next(), differing.next()) {
+            (Some((byte, (a, b))), None) if (a ^ b).count_ones() == 1 => Some(Self {
+                byte,
+                bit: (a ^ b).trailing_zeros() as u8,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_between() {
+        #[track_caller]
+        fn test(name: &str, package: &str, want: Option<(Range<usize>, Range<usize>, &str, &str)>) {
+            assert_eq!(
+                Edit::between(name, package),
+                want.map(|(bytes, chars, original, replacement)| Edit {
+                    bytes,
+                    chars,
+                    original: original.into(),
+                    replacement: replacement.into(),
+                })
+            );
+        }
+
+        test("foo", "foo", None);
+        test("fob", "foo", Some((2..3, 2..3, "b", "o")));
+        test("fo", "foo", Some((2..2, 2..2, "", "o")));
+        test("fooo", "foo", Some((3..4, 3..4, "o", "")));
+        test("ofo", "foo", Some((0..2, 0..2, "of", "fo")));
+        test(
+            "abc-def",
+            "def-abc",
+            Some((0..7, 0..7, "abc-def", "def-abc")),
+        );
+        test("", "foo", Some((0..0, 0..0, "", "foo")));
+        test("cafe", "café", Some((3..4, 3..4, "e", "é")));
+        test("日本語x", "日本語", Some((9..10, 3..4, "x", "")));
+        test("本日語", "日本語", Some((0..6, 0..2, "本日", "日本")));
+    }
+
+    #[test]
+    fn test_flipped_bit_between() {
+        assert_eq!(
+            FlippedBit::between("ac", "ab"),
+            Some(FlippedBit { byte: 1, bit: 0 })
+        );
+        assert_eq!(
+            FlippedBit::between("aB", "ab"),
+            Some(FlippedBit { byte: 1, bit: 5 })
+        );
+        assert_eq!(FlippedBit::between("ab", "ab"), None);
+        assert_eq!(FlippedBit::between("ad", "ab"), None);
+        assert_eq!(FlippedBit::between("acc", "abb"), None);
+        assert_eq!(FlippedBit::between("a", "ab"), None);
+    }
+}
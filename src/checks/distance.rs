@@ -0,0 +1,199 @@
+use tracing::instrument;
+
+use crate::{corpus::DeletionIndex, Corpus, Package};
+
+use super::{Check, Details, Squat};
+
+/// Checks whether the package name is within a given [Damerau-Levenshtein distance][dl] of a
+/// package in the corpus.
+///
+/// This is a catch-all check: unlike the other checks, which each look for a single type of edit,
+/// this will find packages that combine several edits, such as an omitted character and a pair of
+/// swapped characters. As a result, it is also much noisier than the other checks, particularly
+/// for short names, so a minimum name length is applied: see [`EditDistance::with_min_length`].
+///
+/// [dl]: https://en.wikipedia.org/wiki/Damerau%E2%80%93Levenshtein_distance
+pub struct EditDistance {
+    index: DeletionIndex,
+    max_distance: usize,
+    min_length: usize,
+}
+
+impl EditDistance {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "edit-distance";
+
+    /// Instantiates an edit distance check that reports packages in the corpus within
+    /// `max_distance` edits of the package being checked, with a minimum name length of 4.
+    ///
+    /// `names` is generally the same set of names that exist in the top package corpus: a local
+    /// copy is required so that an index of the names can be built during instantiation, rather
+    /// than having to compare the package against every name in the corpus each time the check is
+    /// run. If the corpus provides a [`Corpus::index`] with at least `max_distance`, such as an
+    /// [`IndexedCorpus`][crate::corpus::IndexedCorpus], that index is queried instead.
+    #[instrument(level = "TRACE", skip(names))]
+    pub fn new<'a>(max_distance: usize, names: impl Iterator<Item = &'a str>) -> Self {
+        Self {
+            index: DeletionIndex::new(names, max_distance),
+            max_distance,
+            min_length: 4,
+        }
+    }
+
+    /// Changes the minimum name length, in characters.
+    ///
+    /// If either the package being checked or the package in the corpus has a name shorter than
+    /// this, then no potential squat will be reported. Short names are within a small edit
+    /// distance of a very large number of other names, so this avoids reporting a lot of noise.
+    pub fn with_min_length(mut self, min_length: usize) -> Self {
+        self.min_length = min_length;
+        self
+    }
+}
+
+impl Check for EditDistance {
+    fn check(
+        &self,
+        corpus: &dyn Corpus,
+        name: &str,
+        package: &dyn Package,
+    ) -> crate::Result<Vec<Squat>> {
+        let mut squats = Vec::new();
+
+        if name.chars().count() < self.min_length {
+            return Ok(squats);
+        }
+
+        // Prefer the corpus index, since it reflects the corpus being checked against, but only
+        // if it was built to cover the distance this check needs.
+        let index = corpus
+            .index()
+            .filter(|index| index.max_distance() >= self.max_distance)
+            .unwrap_or(&self.index);
+
+        for (name_to_check, distance) in index.within(name, self.max_distance) {
+            if distance > 0
+                && name_to_check.chars().count() >= self.min_length
+                && corpus.possible_squat(name_to_check, name, package)?
+            {
+                squats.push(Squat::EditDistance {
+                    details: Details::new(name, name_to_check),
+                    distance,
+                });
+            }
+        }
+
+        Ok(squats)
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Edit distance"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names within a small Damerau-Levenshtein edit distance."
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        checks::testutil::{assert_check, check_squats, test_corpus, TestPackage},
+        corpus::IndexedCorpus,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() -> crate::Result<()> {
+        let corpus = ["serde", "tokio", "rand", "regex", "clap", "日本語"];
+
+        #[track_caller]
+        fn test(check: EditDistance, input: &str, want: &[&str]) -> crate::Result<()> {
+            assert_check(check, input, want)
+        }
+
+        let check = || EditDistance::new(2, corpus.iter().copied());
+
+        test(check(), "", &[])?;
+        test(check(), "serde", &[])?;
+        test(check(), "sedre", &["serde"])?;
+
+        // An omission plus a swap.
+        test(check(), "sedr", &["serde"])?;
+
+        // Two transpositions.
+        test(check(), "otkoi", &["tokio"])?;
+
+        // A transposition plus a substitution.
+        test(check(), "otkip", &["tokio"])?;
+
+        // Too far away.
+        test(check(), "clapper", &[])?;
+
+        // Too short, either as input or as a corpus name.
+        test(check(), "ser", &[])?;
+        test(check(), "rands", &["rand"])?;
+        test(check().with_min_length(5), "rands", &[])?;
+        test(check(), "本日語", &[])?;
+        test(check().with_min_length(3), "本日語", &["日本語"])?;
+
+        // Limited distance.
+        test(EditDistance::new(1, corpus.iter().copied()), "sedr", &[])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_distance_recorded() -> crate::Result<()> {
+        let corpus = ["serde", "serve"];
+        let squats = check_squats(
+            EditDistance::new(2, corpus.iter().copied()),
+            &corpus,
+            "sedre",
+        )?;
+
+        assert_eq!(
+            squats,
+            vec![
+                Squat::EditDistance {
+                    details: Details::new("sedre", "serde"),
+                    distance: 1
+                },
+                Squat::EditDistance {
+                    details: Details::new("sedre", "serve"),
+                    distance: 2
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_corpus_index() -> crate::Result<()> {
+        let names = ["serde", "tokio"];
+        let check = EditDistance::new(2, ["serde"].into_iter());
+        let package = TestPackage::default();
+
+        // The corpus index is used when it covers the distance.
+        let corpus = IndexedCorpus::new(test_corpus(&names), names.iter().copied(), 2);
+        assert_eq!(
+            check.check(&corpus, "otkoi", &package)?,
+            vec![Squat::EditDistance {
+                details: Details::new("otkoi", "tokio"),
+                distance: 2
+            }]
+        );
+
+        // Otherwise, the check falls back to its own index.
+        let corpus = IndexedCorpus::new(test_corpus(&names), names.iter().copied(), 1);
+        assert_eq!(check.check(&corpus, "otkoi", &package)?, vec![]);
+
+        Ok(())
+    }
+}
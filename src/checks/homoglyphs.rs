@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+use tracing::instrument;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{BoxError, Corpus, Package};
+
+use super::{Check, Details, Squat};
+
+/// Checks whether the package name looks the same as a package in the corpus once visually
+/// confusable characters are taken into account.
+///
+/// Each name is reduced to a _skeleton_ as defined by [Unicode Technical Standard #39][tr39]: the
+/// name is converted to NFD, each character is replaced by its prototype from the Unicode
+/// confusables table (for example, Cyrillic `а` becomes Latin `a`, `1` and `I` both become `l`,
+/// and `m` becomes `rn`), and the result is converted to NFD again. Packages that share a skeleton
+/// with a corpus package are reported.
+///
+/// Unlike the skeleton defined by TR39, compatibility variants such as fullwidth letters are first
+/// folded with NFKC, since the confusables table doesn't include them. Package names are also
+/// generally case insensitive, so the skeleton is lowercased and then reduced again. As a result,
+/// names that only differ by case will also be reported unless the corpus filters them out.
+///
+/// [tr39]: https://www.unicode.org/reports/tr39/#Confusable_Detection
+pub struct Homoglyphs {
+    skeletons: BTreeMap<String, Vec<usize>>,
+    names: Vec<String>,
+}
+
+impl Homoglyphs {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "homoglyphs";
+
+    /// Instantiates a homoglyph check.
+    ///
+    /// `names` is generally the same set of names that exist in the top package corpus: a local
+    /// copy is required so that the skeletons can be calculated during instantiation, rather than
+    /// having to recalculate them each time the check is run.
+    #[instrument(level = "TRACE", skip(names))]
+    pub fn new<'a>(names: impl Iterator<Item = &'a str>) -> Self {
+        let mut skeletons: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        let mut cloned_names = Vec::new();
+
+        for (i, name) in names.enumerate() {
+            cloned_names.push(name.into());
+            skeletons.entry(skeleton(name)).or_default().push(i);
+        }
+
+        Self {
+            skeletons,
+            names: cloned_names,
+        }
+    }
+}
+
+impl Check for Homoglyphs {
+    fn check(
+        &self,
+        corpus: &dyn Corpus,
+        name: &str,
+        package: &dyn Package,
+    ) -> crate::Result<Vec<Squat>> {
+        let mut squats = Vec::new();
+
+        if let Some(indices) = self.skeletons.get(&skeleton(name)) {
+            for index in indices.iter().copied() {
+                let name_to_check = self.names.get(index).ok_or(Error::OutOfRangeIndex {
+                    index,
+                    len: self.names.len(),
+                })?;
+                if corpus.possible_squat(name_to_check, name, package)? {
+                    squats.push(Squat::Homoglyph(Details::new(name, name_to_check)));
+                }
+            }
+        }
+
+        Ok(squats)
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Homoglyphs"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that replace characters with characters that look alike."
+    }
+}
+
+/// Calculates the skeleton of the given name, as used by [`Homoglyphs`].
+///
+/// Two names with the same skeleton are likely to be visually indistinguishable.
+pub fn skeleton(name: &str) -> String {
+    let folded: String = name.nfkc().collect();
+    let lowercased: String = unicode_security::skeleton(&folded)
+        .flat_map(char::to_lowercase)
+        .collect();
+
+    unicode_security::skeleton(&lowercased).collect()
+}
+
+#[derive(Error, Debug)]
+enum Error {
+    #[error(transparent)]
+    Corpus(#[from] BoxError),
+
+    #[error("unexpected out of range index {index} in vec of length {len}")]
+    OutOfRangeIndex { index: usize, len: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checks::testutil::assert_check;
+
+    use super::*;
+
+    #[test]
+    fn test_skeleton() {
+        #[track_caller]
+        fn same(a: &str, b: &str) {
+            assert_eq!(skeleton(a), skeleton(b), "{a} and {b}");
+        }
+
+        assert_eq!(skeleton("foo"), "foo");
+        same("I0", "lo");
+        same("1o", "lo");
+        same("corn", "com");
+        same("сlар", "clap");
+        same("ｆｏｏ", "foo");
+        same("foo‐bar", "foo-bar");
+
+        // Greek and Cyrillic lookalikes, including uppercase letters.
+        same("ΑΡΙ", "API");
+        same("ѕеrdе", "serde");
+        same("ΤΟΚΙΟ", "TOKIO");
+
+        // Precomposed and decomposed characters are the same once normalized.
+        same("cafe\u{301}", "caf\u{e9}");
+
+        assert_ne!(skeleton("serde"), skeleton("sered"));
+    }
+
+    #[test]
+    fn test_homoglyphs() -> crate::Result<()> {
+        #[track_caller]
+        fn test(corpus: &[&str], input: &str, want: &[&str]) -> crate::Result<()> {
+            assert_check(Homoglyphs::new(corpus.iter().copied()), input, want)
+        }
+
+        let corpus = ["serde", "rand", "log", "tokio", "smol"];
+
+        test(&corpus, "", &[])?;
+        test(&corpus, "serde", &[])?;
+        test(&corpus, "sеrdе", &["serde"])?;
+        test(&corpus, "rnand", &[])?;
+        test(&corpus, "I0g", &["log"])?;
+        test(&corpus, "t0kio", &["tokio"])?;
+        test(&corpus, "srnol", &["smol"])?;
+        test(&corpus, "ｒａｎｄ", &["rand"])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,302 @@
+use std::collections::BTreeSet;
+
+use super::{
+    check_generated, generated_squats_of, Check, Corpus, Details, Generator, Package, Squat,
+};
+
+/// Checks whether a package only differs from a package in the corpus by the English inflection
+/// of one of its words.
+///
+/// Names are split into words on the given delimiters, and each word is inflected in turn. The
+/// inflections considered are:
+///
+/// * plurals: `request` and `requests`, `box` and `boxes`, `library` and `libraries`;
+/// * agent nouns: `parse` and `parser`, `compress` and `compressor`, as well as `parser` and
+///   `parsor`;
+/// * present participles: `parse` and `parsing`, `run` and `running`; and
+/// * `-ify` verbs: `json` and `jsonify`, `pretty` and `prettify`.
+///
+/// Inflections are generated with simple suffix rules, rather than a dictionary, so some of the
+/// names checked won't be real words. This is harmless, since only names that exist in the corpus
+/// are reported.
+pub struct Inflections {
+    delimiters: Vec<char>,
+}
+
+impl Inflections {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "inflections";
+
+    /// Instantiates an inflection check, using each character in `delimiters` as a possible word
+    /// delimiter.
+    pub fn new(delimiters: &str) -> Self {
+        Self {
+            delimiters: delimiters.chars().collect(),
+        }
+    }
+}
+
+impl Check for Inflections {
+    fn check(
+        &self,
+        corpus: &dyn Corpus,
+        name: &str,
+        package: &dyn Package,
+    ) -> crate::Result<Vec<Squat>> {
+        check_generated(self, corpus, name, package)
+    }
+
+    fn squats_of(&self, package: &str) -> Option<Vec<(String, Squat)>> {
+        // Words are inflected in both directions, so the generated names are the candidates, but
+        // not every inflection can be undone.
+        let candidates = self
+            .generate(package)
+            .into_iter()
+            .map(|squat| String::from(squat.package()));
+
+        Some(generated_squats_of(self, package, candidates))
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Inflections"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that change the English inflection of a word, such as its plural."
+    }
+}
+
+impl Generator for Inflections {
+    fn generate(&self, name: &str) -> Vec<Squat> {
+        let mut names_to_check = BTreeSet::new();
+        for (start, end) in words(name, &self.delimiters) {
+            for inflected in inflect(&name[start..end]) {
+                names_to_check.insert(format!(
+                    "{before}{inflected}{after}",
+                    before = &name[..start],
+                    after = &name[end..],
+                ));
+            }
+        }
+
+        names_to_check
+            .iter()
+            .map(|name_to_check| Squat::Inflection(Details::new(name, name_to_check)))
+            .collect()
+    }
+}
+
+/// Returns the byte ranges of the words in `name`.
+fn words(name: &str, delimiters: &[char]) -> Vec<(usize, usize)> {
+    let mut words = Vec::new();
+    let mut start = None;
+
+    for (i, c) in name.char_indices() {
+        if delimiters.contains(&c) {
+            if let Some(start) = start.take() {
+                words.push((start, i));
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+
+    if let Some(start) = start {
+        words.push((start, name.len()));
+    }
+
+    words
+}
+
+/// Returns the possible inflections of `word`, in both directions: for example, `requests` is
+/// inflected to `request`, and `request` to `requests`.
+///
+/// The word itself and empty strings are never returned.
+fn inflect(word: &str) -> BTreeSet<String> {
+    let mut inflections = BTreeSet::new();
+    let mut add = |base: &str, suffix: &str| {
+        inflections.insert(format!("{base}{suffix}"));
+    };
+
+    // Removing inflections.
+    if let Some(base) = word.strip_suffix("ies") {
+        add(base, "y");
+    }
+    if let Some(base) = word.strip_suffix("es") {
+        add(base, "");
+    }
+    if let Some(base) = word.strip_suffix('s') {
+        if !base.ends_with('s') {
+            add(base, "");
+        }
+    }
+    for (suffix, other) in [("er", "or"), ("or", "er")] {
+        if let Some(base) = word.strip_suffix(suffix) {
+            add(base, "");
+            add(base, "e");
+            add(base, other);
+        }
+    }
+    if let Some(base) = word.strip_suffix("ing") {
+        add(base, "");
+        add(base, "e");
+        if let Some(undoubled) = undouble(base) {
+            add(undoubled, "");
+        }
+    }
+    if let Some(base) = word.strip_suffix("ify") {
+        add(base, "");
+        add(base, "y");
+    }
+
+    // Adding inflections.
+    match word.strip_suffix('y') {
+        Some(base) if !base.ends_with(is_vowel) => {
+            add(base, "ies");
+            add(base, "ify");
+        }
+        _ => {}
+    }
+    if word.ends_with(['s', 'x', 'z']) || word.ends_with("ch") || word.ends_with("sh") {
+        add(word, "es");
+    } else {
+        add(word, "s");
+    }
+    match word.strip_suffix('e') {
+        Some(base) => {
+            add(word, "r");
+            add(base, "or");
+            add(base, "ing");
+        }
+        None => {
+            add(word, "er");
+            add(word, "or");
+            add(word, "ing");
+            add(word, "ify");
+            if let Some(last) = doubleable(word) {
+                add(word, &format!("{last}ing"));
+            }
+        }
+    }
+
+    inflections.remove(word);
+    inflections.remove("");
+    inflections
+}
+
+/// Returns `base` without its last character if it ends in a doubled consonant, as in `runn`.
+fn undouble(base: &str) -> Option<&str> {
+    let mut chars = base.chars().rev();
+    match (chars.next(), chars.next()) {
+        (Some(last), Some(prev)) if last == prev && !is_vowel(last) => {
+            Some(&base[..base.len() - last.len_utf8()])
+        }
+        _ => None,
+    }
+}
+
+/// Returns the last character of `word` if it would be doubled before adding `-ing`: that is, if
+/// the word ends in a single vowel followed by a consonant other than `w`, `x`, or `y`, as in
+/// `run`.
+fn doubleable(word: &str) -> Option<char> {
+    let mut chars = word.chars().rev();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(last), Some(vowel), before)
+            if last.is_ascii_alphabetic()
+                && !is_vowel(last)
+                && !matches!(last, 'w' | 'x' | 'y')
+                && is_vowel(vowel)
+                && !before.is_some_and(is_vowel) =>
+        {
+            Some(last)
+        }
+        _ => None,
+    }
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checks::testutil::{assert_squats_of, check_squats};
+
+    use super::*;
+
+    #[test]
+    fn test_words() {
+        assert_eq!(words("", &['-']), vec![]);
+        assert_eq!(words("--", &['-']), vec![]);
+        assert_eq!(words("foo", &['-']), vec![(0, 3)]);
+        assert_eq!(words("-foo--bar-", &['-']), vec![(1, 4), (6, 9)]);
+        assert_eq!(words("日本-語", &['-']), vec![(0, 6), (7, 10)]);
+    }
+
+    #[test]
+    fn test_inflections() -> crate::Result<()> {
+        #[track_caller]
+        fn test(corpus: &[&str], input: &str, want: &[&str]) -> crate::Result<()> {
+            let have: Vec<String> = check_squats(Inflections::new("-_."), corpus, input)?
+                .into_iter()
+                .map(|squat| match squat {
+                    Squat::Inflection(details) => details.package,
+                    _ => panic!("unexpected squat {squat:?}"),
+                })
+                .collect();
+
+            assert_eq!(have, want);
+            Ok(())
+        }
+
+        let corpus = [
+            "request",
+            "box",
+            "library",
+            "parse",
+            "compressor",
+            "run",
+            "json",
+            "pretty",
+            "serde-json",
+            "class",
+        ];
+
+        test(&corpus, "", &[])?;
+        test(&corpus, "request", &[])?;
+        test(&corpus, "requests", &["request"])?;
+        test(&corpus, "boxes", &["box"])?;
+        test(&corpus, "libraries", &["library"])?;
+        test(&corpus, "parser", &["parse"])?;
+        test(&corpus, "parsing", &["parse"])?;
+        test(&corpus, "compress", &["compressor"])?;
+        test(&corpus, "compresser", &["compressor"])?;
+        test(&corpus, "running", &["run"])?;
+        test(&corpus, "jsonify", &["json"])?;
+        test(&corpus, "prettify", &["pretty"])?;
+        test(&corpus, "serdes-json", &["serde-json"])?;
+        test(&corpus, "serde-jsons", &["serde-json"])?;
+        test(&corpus, "serdes-jsons", &[])?;
+        test(&corpus, "clas", &[])?;
+
+        // The inflected word may also be the one in the corpus.
+        test(&["requests", "parser"], "request", &["requests"])?;
+        test(&["requests", "parser"], "parse", &["parser"])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inflections_squats_of() -> crate::Result<()> {
+        assert_squats_of(
+            Inflections::new("-"),
+            "parse",
+            &["parser", "parsor", "parsing", "parses"],
+        )?;
+        assert_squats_of(Inflections::new("-"), "", &[])
+    }
+}
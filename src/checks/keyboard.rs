@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+/// A physical keyboard layout, used to generate typo tables for [`Typos`][super::Typos].
+///
+/// Each row is offset from the left edge of the keyboard by a number of quarter key widths, which
+/// is used to find the keys above and below each key. Keys in adjacent rows are considered to be
+/// neighbours if they overlap horizontally.
+pub(super) struct Layout {
+    rows: &'static [Row],
+}
+
+struct Row {
+    /// The offset of the first key in the row, in quarter key widths.
+    offset: usize,
+
+    /// The characters produced by each key in the row without shift.
+    unshifted: &'static str,
+
+    /// The characters produced by each key in the row with shift. This must have the same number
+    /// of characters as `unshifted`; a space indicates a key that doesn't produce a character.
+    shifted: &'static str,
+}
+
+struct Key {
+    row: usize,
+    x: usize,
+    chars: [Option<char>; 2],
+}
+
+// The width of a key, in quarter key widths.
+const KEY_WIDTH: usize = 4;
+
+impl Layout {
+    /// Generates a typo table from the layout.
+    ///
+    /// Each character is mapped to the characters produced by the neighbouring keys in the same
+    /// shift state, followed by the character produced by the same key in the other shift state.
+    pub(super) fn typos(&self) -> HashMap<char, Vec<String>> {
+        let keys: Vec<Key> = self
+            .rows
+            .iter()
+            .enumerate()
+            .flat_map(|(row, layout)| {
+                layout
+                    .unshifted
+                    .chars()
+                    .zip(layout.shifted.chars())
+                    .enumerate()
+                    .map(move |(i, (unshifted, shifted))| Key {
+                        row,
+                        x: layout.offset + i * KEY_WIDTH,
+                        chars: [unshifted, shifted].map(|c| if c == ' ' { None } else { Some(c) }),
+                    })
+            })
+            .collect();
+
+        let mut typos: HashMap<char, Vec<String>> = HashMap::new();
+        for key in keys.iter() {
+            let neighbours: Vec<&Key> = keys
+                .iter()
+                .filter(|other| key.is_neighbour(other))
+                .collect();
+
+            for (state, c) in key.chars.iter().enumerate() {
+                let Some(c) = c else {
+                    continue;
+                };
+
+                let entry = typos.entry(*c).or_default();
+                let replacements = neighbours
+                    .iter()
+                    .filter_map(|neighbour| neighbour.chars[state])
+                    .chain(key.chars[1 - state]);
+                for replacement in replacements {
+                    let replacement = String::from(replacement);
+                    if !entry.contains(&replacement) {
+                        entry.push(replacement);
+                    }
+                }
+            }
+        }
+
+        typos
+    }
+}
+
+impl Key {
+    fn is_neighbour(&self, other: &Key) -> bool {
+        let dx = self.x.abs_diff(other.x);
+        match self.row.abs_diff(other.row) {
+            0 => dx == KEY_WIDTH,
+            1 => dx < KEY_WIDTH,
+            _ => false,
+        }
+    }
+}
+
+pub(super) static QWERTY: Layout = Layout {
+    rows: &[
+        Row {
+            offset: 0,
+            unshifted: "`1234567890-=",
+            shifted: "~!@#$%^&*()_+",
+        },
+        Row {
+            offset: 6,
+            unshifted: "qwertyuiop[]\\",
+            shifted: "QWERTYUIOP{}|",
+        },
+        Row {
+            offset: 7,
+            unshifted: "asdfghjkl;'",
+            shifted: "ASDFGHJKL:\"",
+        },
+        Row {
+            offset: 9,
+            unshifted: "zxcvbnm,./",
+            shifted: "ZXCVBNM<>?",
+        },
+    ],
+};
+
+pub(super) static QWERTZ: Layout = Layout {
+    rows: &[
+        Row {
+            offset: 0,
+            unshifted: "^1234567890ß´",
+            shifted: "°!\"§$%&/()=?`",
+        },
+        Row {
+            offset: 6,
+            unshifted: "qwertzuiopü+",
+            shifted: "QWERTZUIOPÜ*",
+        },
+        Row {
+            offset: 7,
+            unshifted: "asdfghjklöä#",
+            shifted: "ASDFGHJKLÖÄ'",
+        },
+        Row {
+            offset: 5,
+            unshifted: "<yxcvbnm,.-",
+            shifted: ">YXCVBNM;:_",
+        },
+    ],
+};
+
+pub(super) static AZERTY: Layout = Layout {
+    rows: &[
+        Row {
+            offset: 0,
+            unshifted: "²&é\"'(-è_çà)=",
+            shifted: " 1234567890°+",
+        },
+        Row {
+            offset: 6,
+            unshifted: "azertyuiop^$",
+            shifted: "AZERTYUIOP¨£",
+        },
+        Row {
+            offset: 7,
+            unshifted: "qsdfghjklmù*",
+            shifted: "QSDFGHJKLM%µ",
+        },
+        Row {
+            offset: 5,
+            unshifted: "<wxcvbn,;:!",
+            shifted: ">WXCVBN?./§",
+        },
+    ],
+};
+
+pub(super) static DVORAK: Layout = Layout {
+    rows: &[
+        Row {
+            offset: 0,
+            unshifted: "`1234567890[]",
+            shifted: "~!@#$%^&*(){}",
+        },
+        Row {
+            offset: 6,
+            unshifted: "',.pyfgcrl/=\\",
+            shifted: "\"<>PYFGCRL?+|",
+        },
+        Row {
+            offset: 7,
+            unshifted: "aoeuidhtns-",
+            shifted: "AOEUIDHTNS_",
+        },
+        Row {
+            offset: 9,
+            unshifted: ";qjkxbmwvz",
+            shifted: ":QJKXBMWVZ",
+        },
+    ],
+};
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn test_rows_aligned() {
+        for layout in [&QWERTY, &QWERTZ, &AZERTY, &DVORAK] {
+            for row in layout.rows.iter() {
+                assert_eq!(row.unshifted.chars().count(), row.shifted.chars().count());
+            }
+        }
+    }
+
+    #[test]
+    fn test_typos() {
+        #[track_caller]
+        fn test(layout: &Layout, c: char, want: &str) {
+            let typos = layout.typos();
+            let have: BTreeSet<String> = typos[&c].iter().cloned().collect();
+            assert_eq!(have, want.chars().map(String::from).collect());
+        }
+
+        test(&QWERTY, 'a', "qwszA");
+        test(&QWERTY, 'A', "QWSZa");
+        test(&QWERTY, 'g', "fhtybvG");
+        test(&QWERTY, '1', "`2q!");
+        test(&QWERTY, '-', "0=p[_");
+        test(&QWERTY, '_', ")+P{-");
+        test(&QWERTY, 'm', "n,jkM");
+
+        test(&QWERTZ, 'z', "tu67ghZ");
+        test(&QWERTZ, 'y', "<xasY");
+        test(&QWERTZ, 'ü', "p+ß´öäÜ");
+
+        test(&AZERTY, 'a', "z&éqA");
+        test(&AZERTY, 'é', "&\"az2");
+        test(&AZERTY, '1', "2A&");
+        test(&AZERTY, 'q', "saz<wQ");
+
+        test(&DVORAK, 'e', "ou.pqjE");
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::{BTreeSet, HashMap};
+
+use super::{
+    check_generated, generated_squats_of, util, Check, Corpus, Details, Generator, Package, Squat,
+};
+
+// The maximum number of names that will be checked for a single package. Characters with more
+// than one replacement multiply the number of names to check, so once this is reached, only the
+// first replacement is used for any remaining characters.
+const MAX_NAMES: usize = 64;
+
+/// Checks whether a package name is leetspeak for a package in the corpus: for example,
+/// `r3qu3sts` for `requests`.
+///
+/// Unlike [`Typos`][super::Typos], which replaces one character at a time, every leetspeak
+/// character in the name is replaced at once. The name is also checked with any trailing digits
+/// left as they are, so `py7hon3` is checked against `python3`, as well as `pythone`.
+///
+/// A leading `@` is left as is, since it introduces the scope of a scoped npm package.
+pub struct Leetspeak {
+    table: HashMap<char, Vec<char>>,
+}
+
+impl Leetspeak {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "leetspeak";
+
+    /// Instantiates a leetspeak check.
+    ///
+    /// Each element in `table` maps a leetspeak character to the letters it may stand for. For
+    /// example, if the table only contains `('1', vec!['l', 'i'])`, then a package `1ib1` will be
+    /// checked against `lib1`, `libi`, `iibl`, and so on.
+    pub fn new(table: impl Iterator<Item = (char, Vec<char>)>) -> Self {
+        Self {
+            table: table.collect(),
+        }
+    }
+
+    /// Returns every way of replacing all of the leetspeak characters in `name`.
+    fn decode(&self, name: &str) -> BTreeSet<String> {
+        let mut decoded = vec![String::with_capacity(name.len())];
+
+        for (i, c) in name.chars().enumerate() {
+            let replacements = match self.table.get(&c) {
+                Some(replacements) if (i != 0 || c != '@') && !replacements.is_empty() => {
+                    replacements.as_slice()
+                }
+                _ => std::slice::from_ref(&c),
+            };
+
+            if decoded.len() * replacements.len() > MAX_NAMES {
+                for prefix in decoded.iter_mut() {
+                    prefix.push(replacements[0]);
+                }
+            } else {
+                decoded = decoded
+                    .iter()
+                    .flat_map(|prefix| {
+                        replacements.iter().map(move |replacement| {
+                            let mut name = prefix.clone();
+                            name.push(*replacement);
+                            name
+                        })
+                    })
+                    .collect();
+            }
+        }
+
+        decoded.into_iter().collect()
+    }
+}
+
+impl Default for Leetspeak {
+    /// Instantiates a leetspeak check with the common substitutions of digits and `@` for letters.
+    fn default() -> Self {
+        Self::new(
+            LEETSPEAK
+                .iter()
+                .map(|(c, replacements)| (*c, replacements.to_vec())),
+        )
+    }
+}
+
+impl Check for Leetspeak {
+    fn check(
+        &self,
+        corpus: &dyn Corpus,
+        name: &str,
+        package: &dyn Package,
+    ) -> crate::Result<Vec<Squat>> {
+        check_generated(self, corpus, name, package)
+    }
+
+    /// Only names that replace a single letter of the package are returned.
+    fn squats_of(&self, package: &str) -> Option<Vec<(String, Squat)>> {
+        let mut candidates = Vec::new();
+        for (i, c) in package.chars().enumerate() {
+            for (leet, replacements) in self.table.iter() {
+                if replacements.contains(&c) {
+                    candidates.push(util::replace_chars(package, i, 1, &leet.to_string()));
+                }
+            }
+        }
+        candidates.sort();
+
+        Some(generated_squats_of(self, package, candidates))
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Leetspeak"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that replace letters with digits or symbols that resemble them."
+    }
+}
+
+impl Generator for Leetspeak {
+    fn generate(&self, name: &str) -> Vec<Squat> {
+        let mut names_to_check = self.decode(name);
+        let trimmed = name.trim_end_matches(|c: char| c.is_ascii_digit());
+        if !trimmed.is_empty() && trimmed != name {
+            let version = &name[trimmed.len()..];
+            names_to_check.extend(
+                self.decode(trimmed)
+                    .into_iter()
+                    .map(|decoded| format!("{decoded}{version}")),
+            );
+        }
+        names_to_check.remove(name);
+
+        names_to_check
+            .iter()
+            .map(|name_to_check| Squat::Leetspeak(Details::new(name, name_to_check)))
+            .collect()
+    }
+}
+
+static LEETSPEAK: &[(char, &[char])] = &[
+    ('0', &['o']),
+    ('1', &['l', 'i']),
+    ('3', &['e']),
+    ('4', &['a']),
+    ('5', &['s']),
+    ('7', &['t']),
+    ('@', &['a']),
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::checks::testutil::{assert_check, assert_squats_of};
+
+    use super::*;
+
+    #[test]
+    fn test_leetspeak() -> crate::Result<()> {
+        #[track_caller]
+        fn test(input: &str, want: &[&str]) -> crate::Result<()> {
+            assert_check(Leetspeak::default(), input, want)
+        }
+
+        test("", &[])?;
+        test("requests", &[])?;
+        test("r3qu3sts", &["requests"])?;
+        test("r3qu3575", &["requests", "requ3575"])?;
+        test("1ib", &["lib", "iib"])?;
+        test("c@k3", &["cake", "cak3"])?;
+        test("py7hon3", &["pythone", "python3"])?;
+        test("2", &[])?;
+        test("@babel/c0re", &["@babel/core"])?;
+        test("l33t-1", &["leet-l", "leet-i", "leet-1"])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_names() {
+        let check = Leetspeak::default();
+
+        // Each 1 doubles the number of names, up to the limit.
+        assert_eq!(check.decode("1").len(), 2);
+        assert_eq!(check.decode("111").len(), 8);
+        assert_eq!(check.decode(&"1".repeat(6)).len(), MAX_NAMES);
+        assert_eq!(check.decode(&"1".repeat(10)).len(), MAX_NAMES);
+    }
+
+    #[test]
+    fn test_leetspeak_squats_of() -> crate::Result<()> {
+        #[track_caller]
+        fn test(package: &str, want: &[&str]) -> crate::Result<()> {
+            assert_squats_of(Leetspeak::default(), package, want)
+        }
+
+        test("cake", &["c@ke", "c4ke", "cak3"])?;
+        test("lib", &["1ib", "l1b"])?;
+        test("python3", &["py7hon3", "pyth0n3"])?;
+        test("", &[])
+    }
+}
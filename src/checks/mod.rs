@@ -2,25 +2,51 @@
 //!
 //! To implement a custom check, implement the [`Check`] trait, and have it return one or more
 //! [`Squat`]s when the package may be squatting one or more packages in the corpus.
+//!
+//! Checks that work by rebuilding the package name, rather than by looking it up in an index of
+//! the corpus, also implement the [`Generator`] trait, which returns every candidate name without
+//! querying the corpus. Generators can be composed with [`Chain`] to detect squats that combine
+//! several edits.
 
 use std::fmt::Display;
 
 use crate::{Corpus, Package};
 
+mod affixes;
 mod bitflips;
+mod chain;
+mod delimiters;
+mod details;
+mod distance;
+mod homoglyphs;
+mod inflections;
+mod keyboard;
+mod leetspeak;
 mod omitted;
+mod phonetic;
 mod repeated;
+mod scoped;
 mod swapped;
 mod typos;
 mod util;
 mod version;
 
 #[cfg(test)]
-mod testutil;
+pub(crate) mod testutil;
 
+pub use affixes::Affixes;
 pub use bitflips::Bitflips;
+pub use chain::Chain;
+pub use delimiters::Delimiters;
+pub use details::{Details, Edit, FlippedBit};
+pub use distance::EditDistance;
+pub use homoglyphs::{skeleton, Homoglyphs};
+pub use inflections::Inflections;
+pub use leetspeak::Leetspeak;
 pub use omitted::Omitted;
+pub use phonetic::{Encoding as PhoneticEncoding, Phonetic};
 pub use repeated::Repeated;
+pub use scoped::Scoped;
 pub use swapped::{Characters as SwappedCharacters, Words as SwappedWords};
 pub use typos::Typos;
 pub use version::Version;
@@ -33,19 +59,166 @@ pub trait Check: Sync + Send {
         name: &str,
         package: &dyn Package,
     ) -> crate::Result<Vec<Squat>>;
+
+    /// Returns the names that this check would report as potential squats of the corpus package
+    /// `package`, along with the squat that would be reported for each name.
+    ///
+    /// This is the inverse of [`Check::check`], and can be used to find names worth reserving or
+    /// monitoring. Checks that can't enumerate the names they would report return `None`, which is
+    /// the default implementation. Of the checks provided by typomania, [`Chain`],
+    /// [`EditDistance`], [`Homoglyphs`], [`Phonetic`], and [`Scoped`] return `None`.
+    ///
+    /// Some checks would report too many names to be useful, and only return the most likely:
+    /// [`Delimiters`] only returns names with a single delimiter changed, [`Leetspeak`] only
+    /// returns names with a single letter replaced, and [`Version`] only returns names with a
+    /// single digit version number.
+    fn squats_of(&self, package: &str) -> Option<Vec<(String, Squat)>> {
+        let _ = package;
+        None
+    }
+
+    /// Returns an identifier for the check, such as `swapped-characters`.
+    ///
+    /// Identifiers should be unique within a [`Harness`][crate::Harness], since they are used to
+    /// attribute errors to checks and to disable checks. The checks provided by typomania use the
+    /// value returned by [`Squat::check`] for the squats they report. The default implementation
+    /// returns the name of the implementing type.
+    fn id(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Returns a human readable name for the check, such as `Swapped characters`.
+    ///
+    /// The default implementation returns [`Check::id`].
+    fn name(&self) -> &str {
+        self.id()
+    }
+
+    /// Returns a short, human readable description of the squats the check finds.
+    ///
+    /// The default implementation returns an empty string.
+    fn description(&self) -> &str {
+        ""
+    }
+}
+
+/// A check that can generate the potential squats it looks for without querying a corpus.
+///
+/// Each squat returned by [`Generator::generate`] refers to a candidate name that may or may not
+/// exist: [`check_generated`] can be used to implement [`Check`] by filtering the candidates
+/// against the corpus.
+pub trait Generator: Sync + Send {
+    /// Returns the potential squats of `name` that this check would look for in a corpus.
+    fn generate(&self, name: &str) -> Vec<Squat>;
+}
+
+/// Runs `generator` on `name`, returning the squats of packages that are in the corpus and may be
+/// squatted by `package`.
+pub fn check_generated(
+    generator: &dyn Generator,
+    corpus: &dyn Corpus,
+    name: &str,
+    package: &dyn Package,
+) -> crate::Result<Vec<Squat>> {
+    let mut squats = Vec::new();
+
+    for squat in generator.generate(name) {
+        if corpus.possible_squat(squat.package(), name, package)? {
+            squats.push(squat);
+        }
+    }
+
+    Ok(squats)
+}
+
+/// Implements [`Check::squats_of`] for a generator, given candidate names that may squat
+/// `package`.
+///
+/// Each candidate is run through `generator`, and is kept with the squat that the generator
+/// returns for `package`, if any. This ensures that only names that the check would report are
+/// returned, even when the candidates are a superset.
+pub(crate) fn generated_squats_of(
+    generator: &dyn Generator,
+    package: &str,
+    candidates: impl IntoIterator<Item = String>,
+) -> Vec<(String, Squat)> {
+    let mut squats: Vec<(String, Squat)> = Vec::new();
+
+    for name in candidates {
+        if name == package || squats.iter().any(|(seen, _)| *seen == name) {
+            continue;
+        }
+
+        if let Some(squat) = generator
+            .generate(&name)
+            .into_iter()
+            .find(|squat| squat.package() == package)
+        {
+            squats.push((name, squat));
+        }
+    }
+
+    squats
 }
 
 /// A potential typosquat.
-#[derive(Debug, Clone)]
+///
+/// Each variant identifies the type of squat, and carries [`Details`] of the package that may be
+/// squatted and, where possible, the [`Edit`] between the checked package name and the squatted
+/// package name.
+///
+/// When the `serde` feature is enabled, squats are serialized as objects with a `type` field
+/// identifying the variant in kebab case (for example, `omitted-character`), and a `details`
+/// field containing the [`Details`]. Variants with additional data, such as [`Squat::Bitflip`],
+/// serialize it in further fields alongside `details`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "kebab-case"))]
 pub enum Squat {
-    Bitflip(String),
-    OmittedCharacter(String),
-    RepeatedCharacter(String),
-    SwappedCharacters(String),
-    SwappedWords(String),
-    Typo(String),
-    Version(String),
-    Custom { message: String, package: String },
+    #[cfg_attr(feature = "serde", serde(with = "details_field"))]
+    Affix(Details),
+    Bitflip {
+        details: Details,
+        flip: FlippedBit,
+    },
+    Chain {
+        details: Details,
+        steps: Vec<Squat>,
+    },
+    #[cfg_attr(feature = "serde", serde(with = "details_field"))]
+    Delimiter(Details),
+    EditDistance {
+        details: Details,
+        distance: usize,
+    },
+    #[cfg_attr(feature = "serde", serde(with = "details_field"))]
+    Homoglyph(Details),
+    #[cfg_attr(feature = "serde", serde(with = "details_field"))]
+    Inflection(Details),
+    #[cfg_attr(feature = "serde", serde(with = "details_field"))]
+    Leetspeak(Details),
+    #[cfg_attr(feature = "serde", serde(with = "details_field"))]
+    OmittedCharacter(Details),
+    #[cfg_attr(feature = "serde", serde(with = "details_field"))]
+    Phonetic(Details),
+    #[cfg_attr(feature = "serde", serde(with = "details_field"))]
+    RepeatedCharacter(Details),
+    Scope {
+        details: Details,
+        inner: Option<Box<Squat>>,
+    },
+    #[cfg_attr(feature = "serde", serde(with = "details_field"))]
+    SwappedCharacters(Details),
+    #[cfg_attr(feature = "serde", serde(with = "details_field"))]
+    SwappedWords(Details),
+    #[cfg_attr(feature = "serde", serde(with = "details_field"))]
+    Typo(Details),
+    #[cfg_attr(feature = "serde", serde(with = "details_field"))]
+    Version(Details),
+    Custom {
+        message: String,
+        details: Details,
+    },
 }
 
 impl Squat {
@@ -53,46 +226,202 @@ impl Squat {
     pub fn custom(message: &str, package: &str) -> Self {
         Self::Custom {
             message: message.into(),
-            package: package.into(),
+            details: Details::for_package(package),
         }
     }
 
     /// Retrieves the name of the package that may be squatted.
     pub fn package(&self) -> &str {
+        &self.details().package
+    }
+
+    /// Retrieves the details of the potential squat.
+    pub fn details(&self) -> &Details {
+        match self {
+            Squat::Affix(details) => details,
+            Squat::Bitflip { details, .. } => details,
+            Squat::Chain { details, .. } => details,
+            Squat::Delimiter(details) => details,
+            Squat::EditDistance { details, .. } => details,
+            Squat::Homoglyph(details) => details,
+            Squat::Inflection(details) => details,
+            Squat::Leetspeak(details) => details,
+            Squat::OmittedCharacter(details) => details,
+            Squat::Phonetic(details) => details,
+            Squat::RepeatedCharacter(details) => details,
+            Squat::Scope { details, .. } => details,
+            Squat::SwappedCharacters(details) => details,
+            Squat::SwappedWords(details) => details,
+            Squat::Typo(details) => details,
+            Squat::Version(details) => details,
+            Squat::Custom { details, .. } => details,
+        }
+    }
+
+    fn details_mut(&mut self) -> &mut Details {
+        match self {
+            Squat::Affix(details) => details,
+            Squat::Bitflip { details, .. } => details,
+            Squat::Chain { details, .. } => details,
+            Squat::Delimiter(details) => details,
+            Squat::EditDistance { details, .. } => details,
+            Squat::Homoglyph(details) => details,
+            Squat::Inflection(details) => details,
+            Squat::Leetspeak(details) => details,
+            Squat::OmittedCharacter(details) => details,
+            Squat::Phonetic(details) => details,
+            Squat::RepeatedCharacter(details) => details,
+            Squat::Scope { details, .. } => details,
+            Squat::SwappedCharacters(details) => details,
+            Squat::SwappedWords(details) => details,
+            Squat::Typo(details) => details,
+            Squat::Version(details) => details,
+            Squat::Custom { details, .. } => details,
+        }
+    }
+
+    /// Changes the package that may be squatted to `package`, which should be another spelling of
+    /// the same name, such as the original spelling of a normalized name.
+    ///
+    /// The edit is left as it is, since it describes the change made by the check, not a
+    /// difference in spelling. The last step of a chain is also changed, since it produces the
+    /// squatted package, as is the squat of a single part of a scoped name.
+    pub(crate) fn rename_package(&mut self, package: &str) {
+        match self {
+            Squat::Chain { steps, .. } => {
+                if let Some(last) = steps.last_mut() {
+                    last.rename_package(package);
+                }
+            }
+            Squat::Scope {
+                details,
+                inner: Some(inner),
+            } => {
+                if let (Some((scope, _)), Some((renamed_scope, renamed_unscoped))) =
+                    (scoped::split(&details.package), scoped::split(package))
+                {
+                    if inner.package() == scope {
+                        inner.rename_package(renamed_scope);
+                    } else {
+                        inner.rename_package(renamed_unscoped);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        self.details_mut().package = String::from(package);
+    }
+
+    /// Retrieves the edit between the checked package name and the squatted package name, if
+    /// known.
+    pub fn edit(&self) -> Option<&Edit> {
+        self.details().edit.as_ref()
+    }
+
+    /// Returns the [`Check::id`] of the check that produced this squat, or `custom` for squats
+    /// created with [`Squat::custom`].
+    pub fn check(&self) -> &'static str {
         match self {
-            Squat::Bitflip(package) => package,
-            Squat::OmittedCharacter(package) => package,
-            Squat::RepeatedCharacter(package) => package,
-            Squat::SwappedCharacters(package) => package,
-            Squat::SwappedWords(package) => package,
-            Squat::Typo(package) => package,
-            Squat::Version(package) => package,
-            Squat::Custom {
-                message: _message,
-                package,
-            } => package,
+            Squat::Affix(_) => Affixes::ID,
+            Squat::Bitflip { .. } => Bitflips::ID,
+            Squat::Chain { .. } => Chain::ID,
+            Squat::Delimiter(_) => Delimiters::ID,
+            Squat::EditDistance { .. } => EditDistance::ID,
+            Squat::Homoglyph(_) => Homoglyphs::ID,
+            Squat::Inflection(_) => Inflections::ID,
+            Squat::Leetspeak(_) => Leetspeak::ID,
+            Squat::OmittedCharacter(_) => Omitted::ID,
+            Squat::Phonetic(_) => Phonetic::ID,
+            Squat::RepeatedCharacter(_) => Repeated::ID,
+            Squat::Scope { .. } => Scoped::ID,
+            Squat::SwappedCharacters(_) => SwappedCharacters::ID,
+            Squat::SwappedWords(_) => SwappedWords::ID,
+            Squat::Typo(_) => Typos::ID,
+            Squat::Version(_) => Version::ID,
+            Squat::Custom { .. } => "custom",
         }
     }
 }
 
 impl Display for Squat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let package = self.package();
         match self {
-            Squat::Bitflip(package) => write!(f, "may be a bitflip of {package}"),
-            Squat::OmittedCharacter(package) => write!(f, "omits characters in {package}"),
-            Squat::RepeatedCharacter(package) => write!(f, "repeats characters in {package}"),
-            Squat::SwappedCharacters(package) => write!(f, "swaps characters in {package}"),
-            Squat::SwappedWords(package) => write!(f, "swaps words in {package}"),
-            Squat::Typo(package) => write!(f, "uses a common typo for {package}"),
-            Squat::Version(package) => write!(f, "only changes the version from {package}"),
-            Squat::Custom { message, package } => write!(f, "{message} for {package}"),
+            Squat::Affix(_) => write!(f, "adds or removes an affix from {package}"),
+            Squat::Bitflip { .. } => write!(f, "may be a bitflip of {package}"),
+            Squat::Chain { steps, .. } => {
+                write!(f, "is {} edits away from {package}: ", steps.len())?;
+                for (i, step) in steps.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", then ")?;
+                    }
+                    write!(f, "{step}")?;
+                }
+                Ok(())
+            }
+            Squat::Delimiter(_) => write!(f, "only changes delimiters from {package}"),
+            Squat::EditDistance { distance, .. } => {
+                write!(f, "is {distance} edit(s) away from {package}")
+            }
+            Squat::Homoglyph(_) => write!(f, "looks like {package}"),
+            Squat::Inflection(_) => write!(f, "only changes the inflection of {package}"),
+            Squat::Leetspeak(_) => write!(f, "is leetspeak for {package}"),
+            Squat::OmittedCharacter(_) => write!(f, "omits characters in {package}"),
+            Squat::Phonetic(_) => write!(f, "sounds like {package}"),
+            Squat::RepeatedCharacter(_) => write!(f, "repeats characters in {package}"),
+            Squat::Scope { inner: None, .. } => write!(f, "adds a scope to {package}"),
+            Squat::Scope {
+                inner: Some(inner), ..
+            } => write!(f, "squats {package}: {inner}"),
+            Squat::SwappedCharacters(_) => write!(f, "swaps characters in {package}"),
+            Squat::SwappedWords(_) => write!(f, "swaps words in {package}"),
+            Squat::Typo(_) => write!(f, "uses a common typo for {package}"),
+            Squat::Version(_) => write!(f, "only changes the version from {package}"),
+            Squat::Custom { message, .. } => write!(f, "{message} for {package}"),
         }
     }
 }
 
+/// Serializes the [`Details`] of a newtype [`Squat`] variant in a `details` field, so that every
+/// variant has the same shape.
+#[cfg(feature = "serde")]
+mod details_field {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Details;
+
+    #[derive(Serialize)]
+    struct Borrowed<'a> {
+        details: &'a Details,
+    }
+
+    #[derive(Deserialize)]
+    struct Owned {
+        details: Details,
+    }
+
+    pub(super) fn serialize<S>(details: &Details, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Borrowed { details }.serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D>(deserializer: D) -> Result<Details, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Owned::deserialize(deserializer).map(|owned| owned.details)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use std::collections::HashMap;
+    use std::{
+        collections::HashMap,
+        time::{Duration, SystemTime},
+    };
 
     use super::{testutil::TestPackage, *};
 
@@ -112,6 +441,92 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_rename_package() {
+        // The edit is kept from the original details.
+        let renamed = |name: &str, package: &str, renamed: &str| Details {
+            package: String::from(renamed),
+            ..Details::new(name, package)
+        };
+
+        let mut squat = Squat::SwappedCharacters(Details::new("serde-jsno", "serde-json"));
+        squat.rename_package("serde_json");
+        assert_eq!(
+            squat,
+            Squat::SwappedCharacters(renamed("serde-jsno", "serde-json", "serde_json"))
+        );
+
+        // Squats without an edit don't gain one.
+        let mut squat = Squat::custom("custom", "serde-json");
+        squat.rename_package("serde_json");
+        assert_eq!(squat, Squat::custom("custom", "serde_json"));
+
+        // The last step of a chain is renamed too.
+        let mut squat = Squat::Chain {
+            details: Details::new("sedre-jsno", "serde-json"),
+            steps: vec![
+                Squat::SwappedCharacters(Details::new("sedre-jsno", "serde-jsno")),
+                Squat::SwappedCharacters(Details::new("serde-jsno", "serde-json")),
+            ],
+        };
+        squat.rename_package("serde_json");
+        assert_eq!(
+            squat,
+            Squat::Chain {
+                details: renamed("sedre-jsno", "serde-json", "serde_json"),
+                steps: vec![
+                    Squat::SwappedCharacters(Details::new("sedre-jsno", "serde-jsno")),
+                    Squat::SwappedCharacters(renamed("serde-jsno", "serde-json", "serde_json")),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_rename_scoped_package() {
+        let mut squat = Squat::Scope {
+            details: Details::new("@types/nod", "@types/node"),
+            inner: Some(Box::new(Squat::OmittedCharacter(Details::new(
+                "nod", "node",
+            )))),
+        };
+        squat.rename_package("@Types/Node");
+        assert_eq!(
+            squat,
+            Squat::Scope {
+                details: Details {
+                    package: String::from("@Types/Node"),
+                    ..Details::new("@types/nod", "@types/node")
+                },
+                inner: Some(Box::new(Squat::OmittedCharacter(Details {
+                    package: String::from("Node"),
+                    ..Details::new("nod", "node")
+                }))),
+            }
+        );
+
+        let mut squat = Squat::Scope {
+            details: Details::new("@typse/node", "@types/node"),
+            inner: Some(Box::new(Squat::SwappedCharacters(Details::new(
+                "typse", "types",
+            )))),
+        };
+        squat.rename_package("@Types/Node");
+        assert_eq!(
+            squat,
+            Squat::Scope {
+                details: Details {
+                    package: String::from("@Types/Node"),
+                    ..Details::new("@typse/node", "@types/node")
+                },
+                inner: Some(Box::new(Squat::SwappedCharacters(Details {
+                    package: String::from("Types"),
+                    ..Details::new("typse", "types")
+                }))),
+            }
+        );
+    }
+
     #[test]
     fn test_possible_squat() -> crate::Result<()> {
         let corpus = SimpleCorpus(
@@ -154,4 +569,54 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_possible_squat_metadata() -> crate::Result<()> {
+        let epoch = SystemTime::UNIX_EPOCH;
+        let corpus: HashMap<String, TestPackage> = [(
+            String::from("a"),
+            TestPackage {
+                downloads: Some(100),
+                created_at: Some(epoch + Duration::from_secs(100)),
+                ..TestPackage::new("adam")
+            },
+        )]
+        .into_iter()
+        .collect();
+
+        #[track_caller]
+        fn test(
+            corpus: &HashMap<String, TestPackage>,
+            downloads: Option<u64>,
+            created_at: Option<SystemTime>,
+            want: bool,
+        ) -> crate::Result<()> {
+            let package = TestPackage {
+                downloads,
+                created_at,
+                ..TestPackage::default()
+            };
+            assert_eq!(corpus.possible_squat("a", "x", &package)?, want);
+            Ok(())
+        }
+
+        // Possible squat: no metadata to compare.
+        test(&corpus, None, None, true)?;
+
+        // Possible squat: younger and less popular.
+        test(
+            &corpus,
+            Some(10),
+            Some(epoch + Duration::from_secs(200)),
+            true,
+        )?;
+
+        // Not a possible squat: more popular.
+        test(&corpus, Some(1000), None, false)?;
+
+        // Not a possible squat: older.
+        test(&corpus, None, Some(epoch), false)?;
+
+        Ok(())
+    }
 }
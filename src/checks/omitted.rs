@@ -1,6 +1,6 @@
 use crate::Corpus;
 
-use super::{util, Check, Package, Squat};
+use super::{check_generated, util, Check, Details, Generator, Package, Squat};
 
 /// Checks whether a package only differs from a package in the corpus by omitting one character.
 pub struct Omitted {
@@ -8,6 +8,9 @@ pub struct Omitted {
 }
 
 impl Omitted {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "omitted";
+
     /// Instantiates an omitted character check.
     ///
     /// `alphabet` is the list of characters that are valid in a package name.
@@ -27,22 +30,79 @@ impl Check for Omitted {
     ) -> crate::Result<Vec<Squat>> {
         let mut squats = Vec::new();
 
-        for i in 0..=name.len() {
-            for c in self.alphabet.iter() {
-                let name_to_check = util::rebuild_name(name, i, 0, c);
-                if corpus.possible_squat(&name_to_check, name, package)? {
-                    squats.push(Squat::OmittedCharacter(name_to_check));
+        if let Some(index) = corpus.index() {
+            for name_to_check in index.insertions(name) {
+                if util::inserted_char(name, name_to_check)
+                    .map(|c| {
+                        self.alphabet
+                            .iter()
+                            .any(|a| a.chars().eq(std::iter::once(c)))
+                    })
+                    .unwrap_or(false)
+                    && corpus.possible_squat(name_to_check, name, package)?
+                {
+                    squats.push(Squat::OmittedCharacter(Details::new(name, name_to_check)));
                 }
             }
+
+            return Ok(squats);
+        }
+
+        check_generated(self, corpus, name, package)
+    }
+
+    fn squats_of(&self, package: &str) -> Option<Vec<(String, Squat)>> {
+        let mut squats = Vec::new();
+
+        for (i, c) in package.chars().enumerate() {
+            if self
+                .alphabet
+                .iter()
+                .any(|a| a.chars().eq(std::iter::once(c)))
+            {
+                let name = util::replace_chars(package, i, 1, "");
+                let squat = Squat::OmittedCharacter(Details::new(&name, package));
+                squats.push((name, squat));
+            }
+        }
+
+        Some(squats)
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Omitted characters"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that omit a single character."
+    }
+}
+
+impl Generator for Omitted {
+    fn generate(&self, name: &str) -> Vec<Squat> {
+        let mut squats = Vec::new();
+
+        for i in 0..=name.chars().count() {
+            for c in self.alphabet.iter() {
+                let name_to_check = util::replace_chars(name, i, 0, c);
+                squats.push(Squat::OmittedCharacter(Details::new(name, &name_to_check)));
+            }
         }
 
-        Ok(squats)
+        squats
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::checks::testutil::assert_check;
+    use crate::checks::{
+        testutil::{assert_check, assert_squats_of, check_squats},
+        Edit,
+    };
 
     use super::*;
 
@@ -55,6 +115,43 @@ mod tests {
                 "axyz", "bxyz", "cxyz", "xayz", "xbyz", "xcyz", "xyaz", "xybz", "xycz", "xyza",
                 "xyzb", "xyzc",
             ],
-        )
+        )?;
+
+        assert_check(
+            Omitted::new("éb"),
+            "日本",
+            &["é日本", "b日本", "日é本", "日b本", "日本é", "日本b"],
+        )?;
+
+        assert_check(Omitted::new("🦀"), "a🦀", &["🦀a🦀", "a🦀🦀"])
+    }
+
+    #[test]
+    fn test_omitted_details() -> crate::Result<()> {
+        let squats = check_squats(Omitted::new("abc"), &["xaé"], "xé")?;
+
+        assert_eq!(squats.len(), 1);
+        assert_eq!(squats[0].package(), "xaé");
+        assert_eq!(squats[0].check(), "omitted");
+        assert_eq!(
+            squats[0].edit(),
+            Some(&Edit {
+                bytes: 1..1,
+                chars: 1..1,
+                original: "".into(),
+                replacement: "a".into(),
+            })
+        );
+        assert_eq!(squats[0].to_string(), "omits characters in xaé");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_omitted_squats_of() -> crate::Result<()> {
+        assert_squats_of(Omitted::new("abc"), "xaé", &["xé"])?;
+        assert_squats_of(Omitted::new("abc"), "abba", &["bba", "aba", "abb"])?;
+        assert_squats_of(Omitted::new("é日"), "日é日", &["é日", "日日", "日é"])?;
+        assert_squats_of(Omitted::new("abc"), "", &[])
     }
 }
@@ -0,0 +1,260 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::{
+    phonetic::{double_metaphone, soundex},
+    BoxError, Corpus, Package,
+};
+
+use super::{Check, Details, Squat};
+
+/// The phonetic algorithm used to encode package names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// [Double Metaphone][dm], which produces a primary and an alternate encoding for each word.
+    /// Two names sound identical if either encoding matches.
+    ///
+    /// [dm]: https://en.wikipedia.org/wiki/Metaphone#Double_Metaphone
+    #[default]
+    DoubleMetaphone,
+
+    /// American [Soundex][soundex], which is coarser than Double Metaphone, and will therefore
+    /// match more names.
+    ///
+    /// [soundex]: https://en.wikipedia.org/wiki/Soundex
+    Soundex,
+}
+
+/// Checks whether a package name sounds identical to a package in the corpus: for example,
+/// `colour` and `color`, or `fone` and `phone`.
+///
+/// Names are split into words on the given delimiters, and each word is encoded separately, so
+/// `fone-book` only matches names with two words that sound like `phone` and `book`. Runs of digits
+/// are kept as is, rather than being encoded, so `tokio2` doesn't sound like `tokio`.
+pub struct Phonetic {
+    encoding: Encoding,
+    delimiters: Vec<char>,
+    codes: BTreeMap<String, Vec<usize>>,
+    names: Vec<String>,
+}
+
+impl Phonetic {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "phonetic";
+
+    /// Instantiates a phonetic check that encodes names with `encoding`, using each character in
+    /// `delimiters` as a possible word delimiter.
+    ///
+    /// `names` is generally the same set of names that exist in the top package corpus: a local
+    /// copy is required so that the names can be encoded during instantiation, rather than having
+    /// to encode every name in the corpus each time the check is run.
+    #[instrument(level = "TRACE", skip(names))]
+    pub fn new<'a>(
+        encoding: Encoding,
+        delimiters: &str,
+        names: impl Iterator<Item = &'a str>,
+    ) -> Self {
+        let delimiters: Vec<char> = delimiters.chars().collect();
+        let mut codes: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        let mut cloned_names = Vec::new();
+
+        for (i, name) in names.enumerate() {
+            cloned_names.push(name.into());
+            for code in encode(encoding, &delimiters, name) {
+                codes.entry(code).or_default().push(i);
+            }
+        }
+
+        Self {
+            encoding,
+            delimiters,
+            codes,
+            names: cloned_names,
+        }
+    }
+}
+
+impl Check for Phonetic {
+    fn check(
+        &self,
+        corpus: &dyn Corpus,
+        name: &str,
+        package: &dyn Package,
+    ) -> crate::Result<Vec<Squat>> {
+        let mut squats = Vec::new();
+
+        // Double Metaphone may produce the same corpus name from both encodings, so we need to
+        // collect the indices before checking them.
+        let indices: BTreeSet<usize> = encode(self.encoding, &self.delimiters, name)
+            .iter()
+            .filter_map(|code| self.codes.get(code))
+            .flatten()
+            .copied()
+            .collect();
+
+        for index in indices {
+            let name_to_check = self.names.get(index).ok_or(Error::OutOfRangeIndex {
+                index,
+                len: self.names.len(),
+            })?;
+            if corpus.possible_squat(name_to_check, name, package)? {
+                squats.push(Squat::Phonetic(Details::new(name, name_to_check)));
+            }
+        }
+
+        Ok(squats)
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Phonetic"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that sound the same when spoken."
+    }
+}
+
+/// Encodes `name`, returning one code for each distinct way it may be pronounced.
+///
+/// Names that contain no letters that can be encoded return no codes.
+fn encode(encoding: Encoding, delimiters: &[char], name: &str) -> Vec<String> {
+    let mut primary = Vec::new();
+    let mut alternate = Vec::new();
+    let mut encoded_any = false;
+
+    for word in name.split(|c| delimiters.contains(&c)) {
+        for (is_digits, run) in runs(word) {
+            if is_digits {
+                primary.push(String::from(run));
+                alternate.push(String::from(run));
+                continue;
+            }
+
+            let (p, a) = match encoding {
+                Encoding::DoubleMetaphone => double_metaphone(run),
+                Encoding::Soundex => {
+                    let code = soundex(run);
+                    (code.clone(), code)
+                }
+            };
+            encoded_any |= !p.is_empty() || !a.is_empty();
+            primary.push(p);
+            alternate.push(a);
+        }
+    }
+
+    if !encoded_any {
+        return Vec::new();
+    }
+
+    let primary = primary.join("-");
+    let alternate = alternate.join("-");
+    if primary == alternate {
+        vec![primary]
+    } else {
+        vec![primary, alternate]
+    }
+}
+
+/// Splits a word into alternating runs of ASCII digits and other characters, indicating whether
+/// each run is made up of digits.
+fn runs(word: &str) -> impl Iterator<Item = (bool, &str)> {
+    let mut rest = word;
+    std::iter::from_fn(move || {
+        let is_digits = rest.chars().next()?.is_ascii_digit();
+        let end = rest
+            .find(|c: char| c.is_ascii_digit() != is_digits)
+            .unwrap_or(rest.len());
+        let (run, tail) = rest.split_at(end);
+        rest = tail;
+        Some((is_digits, run))
+    })
+}
+
+#[derive(Error, Debug)]
+enum Error {
+    #[error(transparent)]
+    Corpus(#[from] BoxError),
+
+    #[error("unexpected out of range index {index} in vec of length {len}")]
+    OutOfRangeIndex { index: usize, len: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checks::testutil::assert_check;
+
+    use super::*;
+
+    #[test]
+    fn test_runs() {
+        assert_eq!(runs("").collect::<Vec<_>>(), vec![]);
+        assert_eq!(runs("tokio").collect::<Vec<_>>(), vec![(false, "tokio")]);
+        assert_eq!(
+            runs("base64x").collect::<Vec<_>>(),
+            vec![(false, "base"), (true, "64"), (false, "x")]
+        );
+        assert_eq!(
+            runs("2to3").collect::<Vec<_>>(),
+            vec![(true, "2"), (false, "to"), (true, "3")]
+        );
+    }
+
+    #[test]
+    fn test_double_metaphone() -> crate::Result<()> {
+        #[track_caller]
+        fn test(corpus: &[&str], input: &str, want: &[&str]) -> crate::Result<()> {
+            assert_check(
+                Phonetic::new(Encoding::DoubleMetaphone, "-_.", corpus.iter().copied()),
+                input,
+                want,
+            )
+        }
+
+        let corpus = ["color", "phone-book", "tokio", "smith", "base64", "123"];
+
+        test(&corpus, "", &[])?;
+        test(&corpus, "color", &[])?;
+        test(&corpus, "colour", &["color"])?;
+        test(&corpus, "kolor", &["color"])?;
+        test(&corpus, "fone-book", &["phone-book"])?;
+        test(&corpus, "fone_buk", &["phone-book"])?;
+        test(&corpus, "fonebook", &[])?;
+        test(&corpus, "tokyo", &["tokio"])?;
+        test(&corpus, "tokio2", &[])?;
+        test(&corpus, "base-64", &["base64"])?;
+        test(&corpus, "baze64", &["base64"])?;
+        test(&corpus, "456", &[])?;
+
+        // Matches on the alternate encoding.
+        test(&corpus, "schmidt", &["smith"])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_soundex() -> crate::Result<()> {
+        #[track_caller]
+        fn test(corpus: &[&str], input: &str, want: &[&str]) -> crate::Result<()> {
+            assert_check(
+                Phonetic::new(Encoding::Soundex, "-_.", corpus.iter().copied()),
+                input,
+                want,
+            )
+        }
+
+        let corpus = ["robert", "phone"];
+
+        test(&corpus, "rupert", &["robert"])?;
+        test(&corpus, "robert", &[])?;
+        test(&corpus, "fone", &[])?;
+
+        Ok(())
+    }
+}
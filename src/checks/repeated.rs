@@ -2,11 +2,18 @@ use itertools::Itertools;
 
 use crate::Corpus;
 
-use super::{util, Check, Package, Squat};
+use super::{util, Check, Details, Generator, Package, Squat};
 
 /// Checks whether a package only differs from a package in the corpus by repeating one character.
+///
+/// Any character can be repeated, including non-ASCII characters such as `é` or `日`.
 pub struct Repeated;
 
+impl Repeated {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "repeated";
+}
+
 impl Check for Repeated {
     fn check(
         &self,
@@ -16,22 +23,65 @@ impl Check for Repeated {
     ) -> crate::Result<Vec<Squat>> {
         let mut squats = Vec::new();
 
-        for (i, (a, b)) in name.chars().tuple_windows().enumerate() {
-            if a == b && a.is_ascii() {
-                let name_to_check = util::rebuild_name(name, i, 2, &format!("{a}"));
-                if corpus.possible_squat(&name_to_check, name, package)? {
-                    squats.push(Squat::RepeatedCharacter(name_to_check));
-                }
+        for squat in self.generate(name) {
+            let indexed = corpus
+                .index()
+                .map(|index| index.contains(squat.package()))
+                .unwrap_or(true);
+            if indexed && corpus.possible_squat(squat.package(), name, package)? {
+                squats.push(squat);
             }
         }
 
         Ok(squats)
     }
+
+    fn squats_of(&self, package: &str) -> Option<Vec<(String, Squat)>> {
+        let mut squats = Vec::new();
+
+        for (i, c) in package.chars().enumerate() {
+            let name = util::replace_chars(package, i, 1, &format!("{c}{c}"));
+            let squat = Squat::RepeatedCharacter(Details::new(&name, package));
+            squats.push((name, squat));
+        }
+
+        Some(squats)
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Repeated characters"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that repeat a single character."
+    }
+}
+
+impl Generator for Repeated {
+    fn generate(&self, name: &str) -> Vec<Squat> {
+        let mut squats = Vec::new();
+
+        for (i, (a, b)) in name.chars().tuple_windows().enumerate() {
+            if a == b {
+                let name_to_check = util::replace_chars(name, i, 2, &format!("{a}"));
+                squats.push(Squat::RepeatedCharacter(Details::new(name, &name_to_check)));
+            }
+        }
+
+        squats
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::checks::testutil::assert_check;
+    use crate::checks::{
+        testutil::{assert_check, assert_squats_of, check_squats},
+        Edit,
+    };
 
     use super::*;
 
@@ -50,7 +100,39 @@ mod tests {
         test("abbbc", &["abbc"])?;
         test("abbbbc", &["abbbc"])?;
         test("aaaaaa", &["aaaaa"])?;
+        test("caféé", &["café"])?;
+        test("日日本", &["日本"])?;
+        test("🦀🦀", &["🦀"])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_repeated_multibyte() -> crate::Result<()> {
+        assert_check(Repeated, "ééé", &["éé"])?;
+        assert_check(Repeated, "日本本語", &["日本語"])?;
+        assert_check(Repeated, "a🦀🦀b🦀", &["a🦀b🦀"])?;
+
+        // The edit is reported in both bytes and characters.
+        let squats = check_squats(Repeated, &["日本語"], "日本本語")?;
+        assert_eq!(squats.len(), 1);
+        assert_eq!(
+            squats[0].edit(),
+            Some(&Edit {
+                bytes: 6..9,
+                chars: 2..3,
+                original: "本".into(),
+                replacement: "".into(),
+            })
+        );
 
         Ok(())
     }
+
+    #[test]
+    fn test_repeated_squats_of() -> crate::Result<()> {
+        assert_squats_of(Repeated, "ab", &["aab", "abb"])?;
+        assert_squats_of(Repeated, "aé", &["aaé", "aéé"])?;
+        assert_squats_of(Repeated, "", &[])
+    }
 }
@@ -0,0 +1,432 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{corpus::DeletionIndex, normalize::Normalizer, Corpus, Package};
+
+use super::{Check, Details, Squat};
+
+/// Checks whether a scoped package name, such as `@babel/core`, squats a package in the corpus.
+///
+/// Three types of squat are detected:
+///
+/// 1. A scoped name whose unscoped name is a package in the corpus: for example, `@evil/lodash`
+///    squatting `lodash`.
+/// 1. A scoped name whose scope squats the scope of a package in the corpus with the same
+///    unscoped name, according to the configured checks: for example, `@types-x/node` squatting
+///    `@types/node`.
+/// 1. A scoped name whose unscoped name squats a package in the corpus with the same scope,
+///    according to the configured checks: for example, `@babel/c0re` squatting `@babel/core`.
+///
+/// When the configured checks are run, they only see the scope or unscoped name being checked, so
+/// checks that keep their own copy of the corpus names, such as
+/// [`Homoglyphs`][super::Homoglyphs], should be given the scopes and unscoped names from the
+/// corpus, rather than the full names.
+///
+/// The corpus [`normalizer`][Corpus::normalizer] is available to the configured checks, but an
+/// [`IndexedCorpus`][crate::corpus::IndexedCorpus] index isn't, since it indexes full names. Use
+/// [`Scoped::with_index`] to index the parts of the scoped names instead.
+pub struct Scoped {
+    checks: Vec<Box<dyn Check>>,
+    index: Option<PartIndex>,
+}
+
+impl Scoped {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "scope";
+
+    /// Instantiates a scoped name check with no checks configured for the scope and unscoped
+    /// name parts.
+    pub fn new() -> Self {
+        Self {
+            checks: Vec::new(),
+            index: None,
+        }
+    }
+
+    /// Indexes the parts of the scoped names in `names`, allowing lookups of parts up to
+    /// `max_distance` edits away, so that the configured checks can use a [`DeletionIndex`] when
+    /// checking each part.
+    ///
+    /// `names` should be the full set of names in the corpus; unscoped names are ignored.
+    pub fn with_index<'a>(
+        mut self,
+        names: impl Iterator<Item = &'a str>,
+        max_distance: usize,
+    ) -> Self {
+        self.index = Some(PartIndex::new(names, max_distance));
+        self
+    }
+
+    /// Adds a check to run against the scope and unscoped name parts.
+    pub fn with_check<Chk>(mut self, check: Chk) -> Self
+    where
+        Chk: Check + 'static,
+    {
+        self.checks.push(Box::new(check));
+        self
+    }
+
+    /// Adds a boxed check to run against the scope and unscoped name parts.
+    pub fn with_boxed_check(mut self, check: Box<dyn Check>) -> Self {
+        self.checks.push(check);
+        self
+    }
+}
+
+impl Default for Scoped {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Check for Scoped {
+    fn check(
+        &self,
+        corpus: &dyn Corpus,
+        name: &str,
+        package: &dyn Package,
+    ) -> crate::Result<Vec<Squat>> {
+        let mut squats = Vec::new();
+
+        let Some((scope, unscoped)) = split(name) else {
+            return Ok(squats);
+        };
+
+        if corpus.possible_squat(unscoped, name, package)? {
+            squats.push(Squat::Scope {
+                details: Details::new(name, unscoped),
+                inner: None,
+            });
+        }
+
+        let parts = [
+            (Part::Scope { unscoped }, scope),
+            (Part::Unscoped { scope }, unscoped),
+        ];
+        for (part, checked) in parts.iter() {
+            let part_corpus = PartCorpus {
+                corpus,
+                part: *part,
+                index: self.index.as_ref().and_then(|index| index.get(part)),
+            };
+            for check in self.checks.iter() {
+                for inner in check.check(&part_corpus, checked, package)? {
+                    let name_to_check = part.join(inner.package());
+                    squats.push(Squat::Scope {
+                        details: Details::new(name, &name_to_check),
+                        inner: Some(Box::new(inner)),
+                    });
+                }
+            }
+        }
+
+        Ok(squats)
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Scoped names"
+    }
+
+    fn description(&self) -> &str {
+        "Finds scoped names that squat an unscoped name, a scope, or a name within a scope."
+    }
+}
+
+/// Splits a scoped package name into its scope and unscoped name, or returns `None` if the name
+/// isn't scoped.
+pub(super) fn split(name: &str) -> Option<(&str, &str)> {
+    let (scope, unscoped) = name.strip_prefix('@')?.split_once('/')?;
+    if scope.is_empty() || unscoped.is_empty() {
+        None
+    } else {
+        Some((scope, unscoped))
+    }
+}
+
+/// The part of a scoped name being checked, along with the other part, which remains fixed.
+#[derive(Clone, Copy)]
+enum Part<'a> {
+    Scope { unscoped: &'a str },
+    Unscoped { scope: &'a str },
+}
+
+impl Part<'_> {
+    fn join(&self, part: &str) -> String {
+        match self {
+            Part::Scope { unscoped } => format!("@{part}/{unscoped}"),
+            Part::Unscoped { scope } => format!("@{scope}/{part}"),
+        }
+    }
+}
+
+/// Deletion indexes of the scopes that share each unscoped name, and of the unscoped names that
+/// share each scope.
+struct PartIndex {
+    scopes: HashMap<String, DeletionIndex>,
+    unscoped: HashMap<String, DeletionIndex>,
+}
+
+impl PartIndex {
+    fn new<'a>(names: impl Iterator<Item = &'a str>, max_distance: usize) -> Self {
+        let mut scopes: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        let mut unscoped: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (scope, name) in names.filter_map(split) {
+            scopes.entry(name).or_default().push(scope);
+            unscoped.entry(scope).or_default().push(name);
+        }
+
+        let index = |parts: BTreeMap<&str, Vec<&str>>| {
+            parts
+                .into_iter()
+                .map(|(fixed, parts)| {
+                    let index = DeletionIndex::new(parts.into_iter(), max_distance);
+                    (String::from(fixed), index)
+                })
+                .collect()
+        };
+
+        Self {
+            scopes: index(scopes),
+            unscoped: index(unscoped),
+        }
+    }
+
+    /// Returns the index of the names that can fill `part`.
+    fn get(&self, part: &Part) -> Option<&DeletionIndex> {
+        match part {
+            Part::Scope { unscoped } => self.scopes.get(*unscoped),
+            Part::Unscoped { scope } => self.unscoped.get(*scope),
+        }
+    }
+}
+
+/// A view of a corpus that allows checks to look up packages by a single part of a scoped name.
+struct PartCorpus<'a> {
+    corpus: &'a dyn Corpus,
+    part: Part<'a>,
+    index: Option<&'a DeletionIndex>,
+}
+
+impl Corpus for PartCorpus<'_> {
+    fn contains_name(&self, name: &str) -> crate::Result<bool> {
+        self.corpus.contains_name(&self.part.join(name))
+    }
+
+    fn get(&self, name: &str) -> crate::Result<Option<&dyn Package>> {
+        self.corpus.get(&self.part.join(name))
+    }
+
+    fn possible_squat(
+        &self,
+        corpus_name: &str,
+        package_name: &str,
+        package: &dyn Package,
+    ) -> crate::Result<bool> {
+        self.corpus.possible_squat(
+            &self.part.join(corpus_name),
+            &self.part.join(package_name),
+            package,
+        )
+    }
+
+    fn index(&self) -> Option<&DeletionIndex> {
+        self.index
+    }
+
+    fn normalizer(&self) -> Option<&dyn Normalizer> {
+        self.corpus.normalizer()
+    }
+
+    fn original_name(&self, name: &str) -> Option<&str> {
+        let (scope, unscoped) = split(self.corpus.original_name(&self.part.join(name))?)?;
+        Some(match self.part {
+            Part::Scope { .. } => scope,
+            Part::Unscoped { .. } => unscoped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        checks::{
+            testutil::{check_squats, test_corpus, TestPackage},
+            Affixes, Homoglyphs, Omitted, Typos, Version,
+        },
+        corpus::Normalized,
+        normalize::Npm,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        assert_eq!(split("@babel/core"), Some(("babel", "core")));
+        assert_eq!(split("@a/b/c"), Some(("a", "b/c")));
+        assert_eq!(split("lodash"), None);
+        assert_eq!(split("@babel"), None);
+        assert_eq!(split("@/core"), None);
+        assert_eq!(split("@babel/"), None);
+        assert_eq!(split("babel/core"), None);
+    }
+
+    #[test]
+    fn test_scoped() -> crate::Result<()> {
+        let corpus = ["lodash", "@types/node", "@babel/core", "@babel/cli"];
+
+        #[track_caller]
+        fn test(corpus: &[&str], input: &str, want: &[(&str, Option<&str>)]) -> crate::Result<()> {
+            let check = Scoped::new()
+                .with_check(Homoglyphs::new(
+                    ["babel", "types", "core", "node"].into_iter(),
+                ))
+                .with_check(Omitted::new("abcdefghijklmnopqrstuvwxyz"))
+                .with_check(Version);
+
+            let have: Vec<(String, Option<String>)> = check_squats(check, corpus, input)?
+                .into_iter()
+                .map(|squat| match squat {
+                    Squat::Scope { details, inner } => {
+                        (details.package, inner.map(|inner| inner.to_string()))
+                    }
+                    _ => panic!("unexpected squat {squat:?}"),
+                })
+                .collect();
+
+            assert_eq!(
+                have,
+                want.iter()
+                    .map(|(package, inner)| (String::from(*package), inner.map(String::from)))
+                    .collect::<Vec<_>>()
+            );
+            Ok(())
+        }
+
+        test(&corpus, "lodash", &[])?;
+        test(&corpus, "@babel/core", &[])?;
+        test(&corpus, "@evil/lodash", &[("lodash", None)])?;
+        test(
+            &corpus,
+            "@babeI/core",
+            &[("@babel/core", Some("looks like babel"))],
+        )?;
+        test(
+            &corpus,
+            "@babel/cor",
+            &[("@babel/core", Some("omits characters in core"))],
+        )?;
+        test(
+            &corpus,
+            "@types2/node",
+            &[("@types/node", Some("only changes the version from types"))],
+        )?;
+        test(&corpus, "@babel/node", &[])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scoped_affix() -> crate::Result<()> {
+        let check = Scoped::new().with_check(Affixes::new(std::iter::empty(), ["-x"].into_iter()));
+        let squats = check_squats(check, &["@types/node"], "@types-x/node")?;
+
+        assert_eq!(
+            squats,
+            vec![Squat::Scope {
+                details: Details::new("@types-x/node", "@types/node"),
+                inner: Some(Box::new(Squat::Affix(Details::new("types-x", "types")))),
+            }]
+        );
+        assert_eq!(squats[0].package(), "@types/node");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scoped_index() -> crate::Result<()> {
+        struct Indexed<'a>(&'a DeletionIndex);
+
+        impl Corpus for Indexed<'_> {
+            fn contains_name(&self, name: &str) -> crate::Result<bool> {
+                Ok(self.0.contains(name))
+            }
+
+            fn get(&self, _name: &str) -> crate::Result<Option<&dyn Package>> {
+                Ok(None)
+            }
+
+            fn possible_squat(
+                &self,
+                corpus_name: &str,
+                package_name: &str,
+                _package: &dyn Package,
+            ) -> crate::Result<bool> {
+                Ok(corpus_name != package_name && self.0.contains(corpus_name))
+            }
+
+            fn index(&self) -> Option<&DeletionIndex> {
+                Some(self.0)
+            }
+        }
+
+        let names = ["@babel/core", "@babel/cli", "@types/node", "lodash"];
+        let full = DeletionIndex::new(names.iter().copied(), 1);
+        let corpus = Indexed(&full);
+        let package = TestPackage::default();
+        let check = |scoped: &Scoped, name: &str| -> crate::Result<Vec<String>> {
+            Ok(scoped
+                .check(&corpus, name, &package)?
+                .iter()
+                .map(|squat| String::from(squat.package()))
+                .collect())
+        };
+
+        // Without a part index, the checks fall back to generating candidates.
+        let scoped = Scoped::new()
+            .with_check(Omitted::new("abcdefghijklmnopqrstuvwxyz"))
+            .with_check(Typos::new([('x', vec![String::from("o")])].into_iter()));
+        assert_eq!(check(&scoped, "@babel/cor")?, vec!["@babel/core"]);
+
+        // With one, the checks use the index of each part.
+        let scoped = scoped.with_index(names.iter().copied(), 1);
+        assert_eq!(check(&scoped, "@babel/cor")?, vec!["@babel/core"]);
+        assert_eq!(check(&scoped, "@babel/cxre")?, vec!["@babel/core"]);
+        assert_eq!(check(&scoped, "@typs/node")?, vec!["@types/node"]);
+        assert_eq!(check(&scoped, "@babel/nod")?, Vec::<String>::new());
+
+        let index = scoped.index.as_ref().unwrap();
+        assert!(index
+            .get(&Part::Unscoped { scope: "babel" })
+            .is_some_and(|index| index.contains("cli")));
+        assert!(index.get(&Part::Scope { unscoped: "lodash" }).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_original_name() {
+        let names = ["@Types/Node"];
+        let corpus = Normalized::new(test_corpus(&names), names.iter().copied(), Npm);
+
+        let part = |part| PartCorpus {
+            corpus: &corpus,
+            part,
+            index: None,
+        };
+        assert_eq!(
+            part(Part::Scope { unscoped: "node" }).original_name("types"),
+            Some("Types")
+        );
+        assert_eq!(
+            part(Part::Unscoped { scope: "types" }).original_name("node"),
+            Some("Node")
+        );
+        assert_eq!(
+            part(Part::Unscoped { scope: "types" }).original_name("deno"),
+            None
+        );
+    }
+}
@@ -1,10 +1,17 @@
 use itertools::Itertools;
 
-use super::{util, Check, Corpus, Package, Squat};
+use super::{
+    check_generated, generated_squats_of, util, Check, Corpus, Details, Generator, Package, Squat,
+};
 
 /// Checks whether one or more characters have been swapped in the given package name.
 pub struct Characters;
 
+impl Characters {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "swapped-characters";
+}
+
 impl Check for Characters {
     fn check(
         &self,
@@ -12,18 +19,49 @@ impl Check for Characters {
         name: &str,
         package: &dyn Package,
     ) -> crate::Result<Vec<Squat>> {
+        check_generated(self, corpus, name, package)
+    }
+
+    fn squats_of(&self, package: &str) -> Option<Vec<(String, Squat)>> {
+        // Swapping characters is its own inverse.
+        let squats = self
+            .generate(package)
+            .into_iter()
+            .map(|squat| {
+                let name = String::from(squat.package());
+                let squat = Squat::SwappedCharacters(Details::new(&name, package));
+                (name, squat)
+            })
+            .collect();
+
+        Some(squats)
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Swapped characters"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that swap two adjacent characters."
+    }
+}
+
+impl Generator for Characters {
+    fn generate(&self, name: &str) -> Vec<Squat> {
         let mut squats = Vec::new();
 
         for (i, (a, b)) in name.chars().tuple_windows().enumerate() {
             if a != b {
-                let name_to_check = util::rebuild_name(name, i, 2, &format!("{b}{a}"));
-                if corpus.possible_squat(&name_to_check, name, package)? {
-                    squats.push(Squat::SwappedCharacters(name_to_check));
-                }
+                let name_to_check = util::replace_chars(name, i, 2, &format!("{b}{a}"));
+                squats.push(Squat::SwappedCharacters(Details::new(name, &name_to_check)));
             }
         }
 
-        Ok(squats)
+        squats
     }
 }
 
@@ -34,6 +72,9 @@ pub struct Words {
 }
 
 impl Words {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "swapped-words";
+
     /// Sets up a swapped word check, using each character in `delimiters` as a possible word
     /// delimiter, and a max `k` of 5 (see `[Words::with_max_k]` for more detail).
     pub fn new(delimiters: &str) -> Self {
@@ -67,6 +108,35 @@ impl Check for Words {
         name: &str,
         package: &dyn Package,
     ) -> crate::Result<Vec<Squat>> {
+        check_generated(self, corpus, name, package)
+    }
+
+    fn squats_of(&self, package: &str) -> Option<Vec<(String, Squat)>> {
+        // Reordering the words back is also a swap, but only when all of the words were used, so
+        // the generated names are checked in the other direction.
+        let candidates = self
+            .generate(package)
+            .into_iter()
+            .map(|squat| String::from(squat.package()));
+
+        Some(generated_squats_of(self, package, candidates))
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Swapped words"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that reorder their words."
+    }
+}
+
+impl Generator for Words {
+    fn generate(&self, name: &str) -> Vec<Squat> {
         let mut squats = Vec::new();
 
         let tokens: Vec<String> = name
@@ -77,7 +147,7 @@ impl Check for Words {
         // Short circuit if there's still only one token.
         let num_tokens = tokens.len();
         if num_tokens == 1 {
-            return Ok(squats);
+            return squats;
         }
 
         // Apply the max_k.
@@ -90,19 +160,17 @@ impl Check for Words {
         for case in tokens.into_iter().permutations(k) {
             for delimiter in self.delimiters.iter() {
                 let name_to_check = case.join(&format!("{delimiter}"));
-                if corpus.possible_squat(&name_to_check, name, package)? {
-                    squats.push(Squat::SwappedWords(name_to_check));
-                }
+                squats.push(Squat::SwappedWords(Details::new(name, &name_to_check)));
             }
         }
 
-        Ok(squats)
+        squats
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::checks::testutil::assert_check;
+    use crate::checks::testutil::{assert_check, assert_squats_of};
 
     use super::*;
 
@@ -117,6 +185,10 @@ mod tests {
         test("a", &[])?;
         test("ab", &["ba"])?;
         test("abc", &["bac", "acb"])?;
+        test("éa", &["aé"])?;
+        test("日本語", &["本日語", "日語本"])?;
+        test("a🦀🦀", &["🦀a🦀"])?;
+        test("ééa", &["éaé"])?;
 
         Ok(())
     }
@@ -150,6 +222,8 @@ mod tests {
             ],
         )?;
 
+        test("日本-語", &["日本_語", "語-日本", "語_日本"])?;
+
         // Test max_k.
         assert_check(
             Words::new("-_").with_max_k(2),
@@ -161,4 +235,26 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_characters_squats_of() -> crate::Result<()> {
+        assert_squats_of(Characters, "abc", &["bac", "acb"])?;
+        assert_squats_of(Characters, "aab", &["aba"])?;
+        assert_squats_of(Characters, "日本語", &["本日語", "日語本"])?;
+        assert_squats_of(Characters, "é🦀é", &["🦀éé", "éé🦀"])?;
+        assert_squats_of(Characters, "a", &[])
+    }
+
+    #[test]
+    fn test_words_squats_of() -> crate::Result<()> {
+        assert_squats_of(
+            Words::new("-_"),
+            "abc-def",
+            &["abc_def", "def-abc", "def_abc"],
+        )?;
+
+        // Names with more words than max_k are never reported.
+        assert_squats_of(Words::new("-").with_max_k(2), "a-b-c", &[])?;
+        assert_squats_of(Words::new("-"), "abc", &[])
+    }
 }
@@ -1,16 +1,19 @@
 use std::{
     collections::{HashMap, HashSet},
     sync::RwLock,
+    time::SystemTime,
 };
 
 use crate::AuthorSet;
 
-use super::{Check, Corpus, Package};
+use super::{Check, Corpus, Package, Squat};
 
 #[derive(Debug, Clone, Default)]
 pub struct TestPackage {
     pub authors: HashSet<String>,
     pub description: Option<String>,
+    pub downloads: Option<u64>,
+    pub created_at: Option<SystemTime>,
 }
 
 impl TestPackage {
@@ -18,10 +21,21 @@ impl TestPackage {
         Self {
             authors: [String::from(author)].into_iter().collect(),
             description: None,
+            downloads: None,
+            created_at: None,
         }
     }
 }
 
+/// Returns a corpus with a package for each of `names`, each authored by an author with the same
+/// name as the package.
+pub(crate) fn test_corpus(names: &[&str]) -> HashMap<String, TestPackage> {
+    names
+        .iter()
+        .map(|name| (String::from(*name), TestPackage::new(name)))
+        .collect()
+}
+
 impl AuthorSet for TestPackage {
     fn contains(&self, author: &str) -> bool {
         self.authors.contains(author)
@@ -40,6 +54,14 @@ impl Package for TestPackage {
     fn shared_authors(&self, other: &dyn AuthorSet) -> bool {
         self.authors.iter().any(|author| other.contains(author))
     }
+
+    fn downloads(&self) -> Option<u64> {
+        self.downloads
+    }
+
+    fn created_at(&self) -> Option<SystemTime> {
+        self.created_at
+    }
 }
 
 struct NameTracker {
@@ -121,3 +143,42 @@ where
 
     Ok(())
 }
+
+/// Runs `check` against a corpus containing only the `known` package names, and returns the
+/// potential squats.
+pub(crate) fn check_squats<C>(check: C, known: &[&str], input: &str) -> crate::Result<Vec<Squat>>
+where
+    C: Check,
+{
+    run_check(&check, known, input)
+}
+
+fn run_check(check: &dyn Check, known: &[&str], input: &str) -> crate::Result<Vec<Squat>> {
+    check.check(&test_corpus(known), input, &TestPackage::new(input))
+}
+
+/// Asserts that [`Check::squats_of`] returns exactly the `want` names for `package`, and that
+/// running the check on each name reports the returned squat.
+#[track_caller]
+pub(super) fn assert_squats_of<C>(check: C, package: &str, want: &[&str]) -> crate::Result<()>
+where
+    C: Check,
+{
+    let squats = check
+        .squats_of(package)
+        .expect("check should enumerate the names it would report");
+
+    let have: HashSet<&str> = squats.iter().map(|(name, _)| name.as_str()).collect();
+    let want: HashSet<&str> = want.iter().copied().collect();
+    assert_eq!(have, want);
+
+    for (name, squat) in squats.iter() {
+        assert_eq!(squat.package(), package);
+        assert!(
+            run_check(&check, &[package], name)?.contains(squat),
+            "{name} is not reported as {squat:?}"
+        );
+    }
+
+    Ok(())
+}
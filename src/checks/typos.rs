@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
-use super::{util, Check, Corpus, Package, Squat};
+use super::{
+    keyboard::{Layout, AZERTY, DVORAK, QWERTY, QWERTZ},
+    util, Check, Corpus, Details, Generator, Package, Squat,
+};
 
 /// Checks for common typos.
 ///
@@ -12,6 +15,9 @@ pub struct Typos {
 }
 
 impl Typos {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "typos";
+
     /// Instantiates a typo check.
     ///
     /// Each element in `typos` is used to rebuild the package name when checking. Each character
@@ -23,6 +29,70 @@ impl Typos {
             typos: typos.collect(),
         }
     }
+
+    /// Instantiates a typo check for a US QWERTY keyboard.
+    ///
+    /// Each character is replaced by the characters on the neighbouring keys, and by the character
+    /// on the same key with shift toggled. This includes the digit row and shifted symbols.
+    pub fn qwerty() -> Self {
+        Self::from_layout(&QWERTY)
+    }
+
+    /// Instantiates a typo check for a German QWERTZ keyboard.
+    ///
+    /// See [`Typos::qwerty`] for details of how the typos are generated.
+    pub fn qwertz() -> Self {
+        Self::from_layout(&QWERTZ)
+    }
+
+    /// Instantiates a typo check for a French AZERTY keyboard.
+    ///
+    /// See [`Typos::qwerty`] for details of how the typos are generated.
+    pub fn azerty() -> Self {
+        Self::from_layout(&AZERTY)
+    }
+
+    /// Instantiates a typo check for a US Dvorak keyboard.
+    ///
+    /// See [`Typos::qwerty`] for details of how the typos are generated.
+    pub fn dvorak() -> Self {
+        Self::from_layout(&DVORAK)
+    }
+
+    /// Merges the typos from `other` into this check.
+    ///
+    /// This can be used to combine tables for several keyboard layouts, or to add custom typos to
+    /// a keyboard layout. Duplicate typos are only checked once.
+    pub fn merge(mut self, other: Self) -> Self {
+        for (c, typos) in other.typos.into_iter() {
+            let entry = self.typos.entry(c).or_default();
+            for typo in typos.into_iter() {
+                if !entry.contains(&typo) {
+                    entry.push(typo);
+                }
+            }
+        }
+
+        self
+    }
+
+    fn from_layout(layout: &Layout) -> Self {
+        Self {
+            typos: layout.typos(),
+        }
+    }
+}
+
+impl Default for Typos {
+    /// Instantiates a typo check with a table of easily confused characters that has been used
+    /// with crates.io.
+    fn default() -> Self {
+        Self::new(
+            TYPOS
+                .iter()
+                .map(|(c, typos)| (*c, typos.iter().map(|typo| typo.to_string()).collect())),
+        )
+    }
 }
 
 impl Check for Typos {
@@ -33,25 +103,152 @@ impl Check for Typos {
         package: &dyn Package,
     ) -> crate::Result<Vec<Squat>> {
         let mut squats = Vec::new();
+        let index = corpus.index();
 
         for (i, c) in name.chars().enumerate() {
             if let Some(typos) = self.typos.get(&c) {
                 for typo in typos.iter() {
-                    let name_to_check = util::rebuild_name(name, i, 1, typo);
+                    // Single character substitutions are looked up in the index below, if there
+                    // is one.
+                    if index.is_some() && typo.chars().count() == 1 {
+                        continue;
+                    }
+
+                    let name_to_check = util::replace_chars(name, i, 1, typo);
                     if corpus.possible_squat(&name_to_check, name, package)? {
-                        squats.push(Squat::Typo(name_to_check));
+                        squats.push(Squat::Typo(Details::new(name, &name_to_check)));
                     }
                 }
             }
         }
 
+        if let Some(index) = index {
+            for (i, name_to_check) in index.substitutions(name) {
+                let is_typo = match (name.chars().nth(i), name_to_check.chars().nth(i)) {
+                    (Some(c), Some(replacement)) => self.typos.get(&c).is_some_and(|typos| {
+                        typos
+                            .iter()
+                            .any(|typo| typo.chars().eq(std::iter::once(replacement)))
+                    }),
+                    _ => false,
+                };
+
+                if is_typo && corpus.possible_squat(name_to_check, name, package)? {
+                    squats.push(Squat::Typo(Details::new(name, name_to_check)));
+                }
+            }
+        }
+
         Ok(squats)
     }
+
+    fn squats_of(&self, package: &str) -> Option<Vec<(String, Squat)>> {
+        let mut squats = Vec::new();
+        let mut push = |name: String| {
+            let squat = Squat::Typo(Details::new(&name, package));
+            squats.push((name, squat));
+        };
+
+        // Find every place that each typo could have come from.
+        for (c, typos) in self.typos.iter() {
+            for typo in typos.iter() {
+                let replacement = c.to_string();
+                if typo.is_empty() {
+                    for i in 0..=package.chars().count() {
+                        push(util::replace_chars(package, i, 0, &replacement));
+                    }
+                    continue;
+                }
+
+                let len = typo.chars().count();
+                for (i, (offset, _)) in package.char_indices().enumerate() {
+                    if package[offset..].starts_with(typo.as_str()) {
+                        push(util::replace_chars(package, i, len, &replacement));
+                    }
+                }
+            }
+        }
+
+        Some(squats)
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Typos"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that replace characters with common typos."
+    }
+}
+
+impl Generator for Typos {
+    fn generate(&self, name: &str) -> Vec<Squat> {
+        let mut squats = Vec::new();
+
+        for (i, c) in name.chars().enumerate() {
+            if let Some(typos) = self.typos.get(&c) {
+                for typo in typos.iter() {
+                    let name_to_check = util::replace_chars(name, i, 1, typo);
+                    squats.push(Squat::Typo(Details::new(name, &name_to_check)));
+                }
+            }
+        }
+
+        squats
+    }
 }
 
+// This is based on a pre-existing list we've used with crates.io for "easily confused characters".
+// (I'm not really sure that I consider all of these easily confused, but it's better than nothing.)
+static TYPOS: &[(char, &[&str])] = &[
+    ('1', &["2", "q", "i", "l"]),
+    ('2', &["1", "q", "w", "3"]),
+    ('3', &["2", "w", "e", "4"]),
+    ('4', &["3", "e", "r", "5"]),
+    ('5', &["4", "r", "t", "6", "s"]),
+    ('6', &["5", "t", "y", "7"]),
+    ('7', &["6", "y", "u", "8"]),
+    ('8', &["7", "u", "i", "9"]),
+    ('9', &["8", "i", "o", "0"]),
+    ('0', &["9", "o", "p", "-"]),
+    ('-', &["_", "0", "p", ".", ""]),
+    ('_', &["-", "0", "p", ".", ""]),
+    ('q', &["1", "2", "w", "a"]),
+    ('w', &["2", "3", "e", "s", "a", "q", "vv"]),
+    ('e', &["3", "4", "r", "d", "s", "w"]),
+    ('r', &["4", "5", "t", "f", "d", "e"]),
+    ('t', &["5", "6", "y", "g", "f", "r"]),
+    ('y', &["6", "7", "u", "h", "t", "i"]),
+    ('u', &["7", "8", "i", "j", "y", "v"]),
+    ('i', &["1", "8", "9", "o", "l", "k", "j", "u", "y"]),
+    ('o', &["9", "0", "p", "l", "i"]),
+    ('p', &["0", "-", "o"]),
+    ('a', &["q", "w", "s", "z"]),
+    ('s', &["w", "d", "x", "z", "a", "5"]),
+    ('d', &["e", "r", "f", "c", "x", "s"]),
+    ('f', &["r", "g", "v", "c", "d"]),
+    ('g', &["t", "h", "b", "v", "f"]),
+    ('h', &["y", "j", "n", "b", "g"]),
+    ('j', &["u", "i", "k", "m", "n", "h"]),
+    ('k', &["i", "o", "l", "m", "j"]),
+    ('l', &["i", "o", "p", "k", "1"]),
+    ('z', &["a", "s", "x"]),
+    ('x', &["z", "s", "d", "c"]),
+    ('c', &["x", "d", "f", "v"]),
+    ('v', &["c", "f", "g", "b", "u"]),
+    ('b', &["v", "g", "h", "n"]),
+    ('n', &["b", "h", "j", "m"]),
+    ('m', &["n", "j", "k", "rn"]),
+    ('.', &["-", "_", ""]),
+];
+
 #[cfg(test)]
 mod tests {
-    use crate::checks::testutil::assert_check;
+    use crate::checks::testutil::{assert_check, assert_squats_of};
 
     use super::*;
 
@@ -70,7 +267,81 @@ mod tests {
         test("x", &[])?;
         test("a", &["ab", "b"])?;
         test("xax", &["xabx", "xbx"])?;
+        test("éa", &["éab", "éb"])?;
+        test("日a本", &["日ab本", "日b本"])?;
+
+        assert_check(
+            Typos::new([('é', vec![String::from("e"), String::from("è")])].into_iter()),
+            "café",
+            &["cafe", "cafè"],
+        )?;
 
         Ok(())
     }
+
+    #[test]
+    fn test_layouts() -> crate::Result<()> {
+        assert_check(
+            Typos::qwerty(),
+            "ab",
+            &["qb", "wb", "sb", "zb", "Ab", "av", "ag", "ah", "an", "aB"],
+        )?;
+        assert_check(Typos::azerty(), "é", &["&", "\"", "a", "z", "2"])?;
+        assert_check(Typos::qwertz(), "z", &["t", "u", "6", "7", "g", "h", "Z"])?;
+        assert_check(Typos::dvorak(), "-", &["s", "/", "=", "z", "_"])?;
+
+        assert_check(Typos::default(), "m", &["n", "j", "k", "rn"])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() -> crate::Result<()> {
+        let typos = |c: char, typos: &[&str]| {
+            Typos::new([(c, typos.iter().map(|typo| String::from(*typo)).collect())].into_iter())
+        };
+
+        assert_check(
+            typos('a', &["b", "c"])
+                .merge(typos('a', &["c", "d"]))
+                .merge(typos('x', &["y"])),
+            "ax",
+            &["bx", "cx", "dx", "ay"],
+        )?;
+
+        assert_check(
+            Typos::qwerty().merge(Typos::azerty()),
+            "q",
+            &["1", "2", "w", "a", "Q", "s", "z", "<"],
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_typos_squats_of() -> crate::Result<()> {
+        let typos = Typos::new(
+            [
+                ('a', vec![String::from("b"), String::from("cd")]),
+                ('x', vec![String::new()]),
+            ]
+            .into_iter(),
+        );
+
+        assert_squats_of(typos, "bcd", &["acd", "ba", "xbcd", "bxcd", "bcxd", "bcdx"])?;
+
+        let typos = Typos::new(
+            [
+                ('é', vec![String::from("e"), String::from("日本")]),
+                ('語', vec![String::new()]),
+            ]
+            .into_iter(),
+        );
+
+        assert_squats_of(
+            typos,
+            "e日本",
+            &["é日本", "eé", "語e日本", "e語日本", "e日語本", "e日本語"],
+        )
+    }
 }
@@ -1,25 +1,78 @@
-pub(super) fn rebuild_name(orig: &str, index: usize, replace: usize, replacement: &str) -> String {
+/// Rebuilds `orig` with `replace` characters starting at the character offset `index` replaced by
+/// `replacement`.
+///
+/// Both `index` and `replace` are counted in characters, not bytes, so this is safe to use on
+/// names containing multi-byte characters. Offsets beyond the end of `orig` are clamped to the end
+/// of the string.
+pub(super) fn replace_chars(orig: &str, index: usize, replace: usize, replacement: &str) -> String {
+    let start = byte_offset(orig, index);
+    let end = start + byte_offset(&orig[start..], replace);
+
     format!(
         "{before}{replacement}{after}",
-        before = &orig[0..index],
-        after = if let Some(after) = orig.get(index + replace..) {
-            after
-        } else {
-            ""
-        }
+        before = &orig[..start],
+        after = &orig[end..],
     )
 }
 
+/// Returns the character that was inserted into `orig` to produce `longer`, assuming that `longer`
+/// is exactly one character longer than `orig`.
+pub(super) fn inserted_char(orig: &str, longer: &str) -> Option<char> {
+    let mut longer_chars = longer.chars();
+    for c in orig.chars() {
+        match longer_chars.next() {
+            Some(l) if l == c => continue,
+            other => return other,
+        }
+    }
+
+    longer_chars.next()
+}
+
+/// Returns the byte offset of the character at character offset `index` within `s`, or the length
+/// of `s` if `index` is beyond the end of the string.
+fn byte_offset(s: &str, index: usize) -> usize {
+    s.char_indices()
+        .nth(index)
+        .map(|(offset, _)| offset)
+        .unwrap_or(s.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_rebuild_name() {
-        assert_eq!("foobar", rebuild_name("foobar", 3, 0, ""));
-        assert_eq!("fooxbar", rebuild_name("foobar", 3, 0, "x"));
-        assert_eq!("fooxar", rebuild_name("foobar", 3, 1, "x"));
-        assert_eq!("fxbar", rebuild_name("foobar", 1, 2, "x"));
-        assert_eq!("fxxbar", rebuild_name("foobar", 1, 2, "xx"));
+    fn test_replace_chars() {
+        assert_eq!("foobar", replace_chars("foobar", 3, 0, ""));
+        assert_eq!("fooxbar", replace_chars("foobar", 3, 0, "x"));
+        assert_eq!("fooxar", replace_chars("foobar", 3, 1, "x"));
+        assert_eq!("fxbar", replace_chars("foobar", 1, 2, "x"));
+        assert_eq!("fxxbar", replace_chars("foobar", 1, 2, "xx"));
+        assert_eq!("foobarx", replace_chars("foobar", 6, 0, "x"));
+        assert_eq!("foobx", replace_chars("foobar", 4, 5, "x"));
+        assert_eq!("foobarx", replace_chars("foobar", 10, 1, "x"));
+    }
+
+    #[test]
+    fn test_inserted_char() {
+        assert_eq!(Some('x'), inserted_char("foo", "xfoo"));
+        assert_eq!(Some('x'), inserted_char("foo", "foxo"));
+        assert_eq!(Some('x'), inserted_char("foo", "foox"));
+        assert_eq!(Some('o'), inserted_char("foo", "fooo"));
+        assert_eq!(Some('é'), inserted_char("caf", "café"));
+        assert_eq!(Some('本'), inserted_char("日語", "日本語"));
+        assert_eq!(None, inserted_char("foo", "foo"));
+    }
+
+    #[test]
+    fn test_replace_chars_multibyte() {
+        assert_eq!("cafxé", replace_chars("café", 3, 0, "x"));
+        assert_eq!("cafx", replace_chars("café", 3, 1, "x"));
+        assert_eq!("cafée", replace_chars("café", 4, 0, "e"));
+        assert_eq!("日x語", replace_chars("日本語", 1, 1, "x"));
+        assert_eq!("本日語", replace_chars("日本語", 0, 2, "本日"));
+        assert_eq!("a🦀c", replace_chars("abc", 1, 1, "🦀"));
+        assert_eq!("🦀", replace_chars("🦀🦀", 1, 1, ""));
     }
 }
@@ -1,9 +1,16 @@
-use super::{Check, Corpus, Package, Squat};
+use super::{
+    check_generated, generated_squats_of, Check, Corpus, Details, Generator, Package, Squat,
+};
 
 /// Checks whether a package only differs from a package in the corpus by omitting a version
 /// number.
 pub struct Version;
 
+impl Version {
+    /// The identifier returned by [`Check::id`] and [`Squat::check`].
+    pub const ID: &'static str = "version";
+}
+
 impl Check for Version {
     fn check(
         &self,
@@ -11,26 +18,53 @@ impl Check for Version {
         name: &str,
         package: &dyn Package,
     ) -> crate::Result<Vec<Squat>> {
+        check_generated(self, corpus, name, package)
+    }
+
+    /// Only names that add `-` or a single digit version to the package are returned.
+    fn squats_of(&self, package: &str) -> Option<Vec<(String, Squat)>> {
+        let candidates = VERSION_SUFFIXES
+            .iter()
+            .map(|suffix| format!("{package}{suffix}"));
+
+        Some(generated_squats_of(self, package, candidates))
+    }
+
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn name(&self) -> &str {
+        "Version"
+    }
+
+    fn description(&self) -> &str {
+        "Finds names that only add or change a version number."
+    }
+}
+
+impl Generator for Version {
+    fn generate(&self, name: &str) -> Vec<Squat> {
         let trimmed = name
-            .trim_end_matches(&['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'])
+            .trim_end_matches(['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'])
             .trim_end_matches('-');
 
-        Ok(
-            if !trimmed.is_empty()
-                && trimmed != name
-                && corpus.possible_squat(trimmed, name, package)?
-            {
-                vec![Squat::Version(trimmed.into())]
-            } else {
-                Vec::new()
-            },
-        )
+        if !trimmed.is_empty() && trimmed != name {
+            vec![Squat::Version(Details::new(name, trimmed))]
+        } else {
+            Vec::new()
+        }
     }
 }
 
+static VERSION_SUFFIXES: &[&str] = &[
+    "-", "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "-0", "-1", "-2", "-3", "-4", "-5",
+    "-6", "-7", "-8", "-9",
+];
+
 #[cfg(test)]
 mod tests {
-    use crate::checks::testutil::assert_check;
+    use crate::checks::testutil::{assert_check, assert_squats_of};
 
     use super::*;
 
@@ -44,7 +78,33 @@ mod tests {
         assert_check(Version, "abc-234", &["abc"])?;
         assert_check(Version, "abc-", &["abc"])?;
         assert_check(Version, "abc0", &["abc"])?;
+        assert_check(Version, "café-2", &["café"])?;
+        assert_check(Version, "日本2", &["日本"])?;
 
         Ok(())
     }
+
+    #[test]
+    fn test_version_squats_of() -> crate::Result<()> {
+        assert_squats_of(
+            Version,
+            "abc",
+            &[
+                "abc-", "abc0", "abc1", "abc2", "abc3", "abc4", "abc5", "abc6", "abc7", "abc8",
+                "abc9", "abc-0", "abc-1", "abc-2", "abc-3", "abc-4", "abc-5", "abc-6", "abc-7",
+                "abc-8", "abc-9",
+            ],
+        )?;
+
+        // Only versions separated from a trailing digit are removed as a whole.
+        assert_squats_of(
+            Version,
+            "abc2",
+            &[
+                "abc2-", "abc2-0", "abc2-1", "abc2-2", "abc2-3", "abc2-4", "abc2-5", "abc2-6",
+                "abc2-7", "abc2-8", "abc2-9",
+            ],
+        )?;
+        assert_squats_of(Version, "", &[])
+    }
 }
@@ -0,0 +1,158 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::BuildHasher,
+    sync::Arc,
+};
+
+use crate::{normalize::Normalizer, Package, Result};
+
+use super::{Corpus, DeletionIndex};
+
+/// Package names are looked up as map keys.
+///
+/// Since [`Package`] is implemented for `Box<dyn Package>`, this also covers
+/// `HashMap<String, Box<dyn Package>>`, which can hold packages of different types.
+impl<P, S> Corpus for HashMap<String, P, S>
+where
+    P: Package,
+    S: BuildHasher + Send + Sync,
+{
+    fn contains_name(&self, name: &str) -> Result<bool> {
+        Ok(self.contains_key(name))
+    }
+
+    fn get(&self, name: &str) -> Result<Option<&dyn Package>> {
+        Ok(HashMap::get(self, name).map(|package| package as &dyn Package))
+    }
+}
+
+/// Package names are looked up as map keys.
+impl<P> Corpus for BTreeMap<String, P>
+where
+    P: Package,
+{
+    fn contains_name(&self, name: &str) -> Result<bool> {
+        Ok(self.contains_key(name))
+    }
+
+    fn get(&self, name: &str) -> Result<Option<&dyn Package>> {
+        Ok(BTreeMap::get(self, name).map(|package| package as &dyn Package))
+    }
+}
+
+/// Allows a corpus to be shared, for example between several harnesses.
+impl<C> Corpus for Arc<C>
+where
+    C: Corpus + ?Sized,
+{
+    fn contains_name(&self, name: &str) -> Result<bool> {
+        self.as_ref().contains_name(name)
+    }
+
+    fn get(&self, name: &str) -> Result<Option<&dyn Package>> {
+        self.as_ref().get(name)
+    }
+
+    fn possible_squat(
+        &self,
+        corpus_name: &str,
+        package_name: &str,
+        package: &dyn Package,
+    ) -> Result<bool> {
+        self.as_ref()
+            .possible_squat(corpus_name, package_name, package)
+    }
+
+    fn index(&self) -> Option<&DeletionIndex> {
+        self.as_ref().index()
+    }
+
+    fn normalizer(&self) -> Option<&dyn Normalizer> {
+        self.as_ref().normalizer()
+    }
+
+    fn original_name(&self, name: &str) -> Option<&str> {
+        self.as_ref().original_name(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{checks::testutil::TestPackage, normalize::CratesIo};
+
+    use super::{super::Normalized, *};
+
+    fn assert_corpus(corpus: &dyn Corpus) -> Result<()> {
+        assert!(corpus.contains_name("serde")?);
+        assert!(!corpus.contains_name("sedre")?);
+        assert!(corpus.get("serde")?.is_some());
+        assert!(corpus.get("sedre")?.is_none());
+        assert!(corpus.possible_squat("serde", "sedre", &TestPackage::default())?);
+        assert!(!corpus.possible_squat("serde", "sedre", &TestPackage::new("serde"))?);
+
+        Ok(())
+    }
+
+    fn packages<P>(wrap: impl Fn(TestPackage) -> P) -> impl Iterator<Item = (String, P)> {
+        ["serde", "tokio"]
+            .into_iter()
+            .map(move |name| (String::from(name), wrap(TestPackage::new(name))))
+    }
+
+    #[test]
+    fn test_hash_map() -> Result<()> {
+        let corpus: HashMap<String, TestPackage> = packages(|package| package).collect();
+        assert_corpus(&corpus)
+    }
+
+    #[test]
+    fn test_btree_map() -> Result<()> {
+        let corpus: BTreeMap<String, TestPackage> = packages(|package| package).collect();
+        assert_corpus(&corpus)
+    }
+
+    #[test]
+    fn test_boxed_packages() -> Result<()> {
+        let mut corpus: HashMap<String, Box<dyn Package>> = packages(|package| {
+            let package: Box<dyn Package> = Box::new(package);
+            package
+        })
+        .collect();
+        assert_corpus(&corpus)?;
+
+        // Metadata is forwarded through the box.
+        corpus.insert(
+            String::from("sedre"),
+            Box::new(TestPackage {
+                downloads: Some(10),
+                ..TestPackage::default()
+            }),
+        );
+        assert_eq!(corpus["sedre"].downloads(), Some(10));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_arc() -> Result<()> {
+        let map: HashMap<String, TestPackage> = packages(|package| package).collect();
+        let corpus = Arc::new(map);
+        assert_corpus(&corpus)?;
+        assert_corpus(&Arc::clone(&corpus))?;
+
+        let dynamic: Arc<dyn Corpus> = corpus;
+        assert_corpus(&dynamic)?;
+
+        // Normalization is forwarded.
+        let names: Vec<String> = packages(|package| package).map(|(name, _)| name).collect();
+        let normalized = Arc::new(Normalized::new(
+            packages(|package| package).collect::<HashMap<_, _>>(),
+            names.iter().map(String::as_str),
+            CratesIo,
+        ));
+        assert!(normalized.normalizer().is_some());
+        assert!(normalized.contains_name("SERDE")?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::{BTreeSet, HashMap};
+
+use tracing::instrument;
+
+use crate::{distance::damerau_levenshtein, normalize::Normalizer, Package, Result};
+
+use super::Corpus;
+
+/// A [`Corpus`] wrapper that maintains a [`DeletionIndex`] of the names in the corpus.
+///
+/// Checks that can take advantage of the index — currently [`Omitted`][crate::checks::Omitted],
+/// [`Repeated`][crate::checks::Repeated], and [`Typos`][crate::checks::Typos] — will use it
+/// instead of generating every possible candidate name and querying the corpus for each one,
+/// which makes checking against large corpora considerably faster.
+pub struct IndexedCorpus<C>
+where
+    C: Corpus,
+{
+    corpus: C,
+    index: DeletionIndex,
+}
+
+impl<C> IndexedCorpus<C>
+where
+    C: Corpus,
+{
+    /// Wraps `corpus` with an index of `names`, allowing lookups of names up to `max_distance`
+    /// edits away.
+    ///
+    /// `names` should be the full set of names in the corpus.
+    pub fn new<'a>(corpus: C, names: impl Iterator<Item = &'a str>, max_distance: usize) -> Self {
+        Self {
+            corpus,
+            index: DeletionIndex::new(names, max_distance),
+        }
+    }
+
+    /// Returns the wrapped corpus.
+    pub fn into_inner(self) -> C {
+        self.corpus
+    }
+}
+
+impl<C> Corpus for IndexedCorpus<C>
+where
+    C: Corpus,
+{
+    fn contains_name(&self, name: &str) -> Result<bool> {
+        self.corpus.contains_name(name)
+    }
+
+    fn get(&self, name: &str) -> Result<Option<&dyn Package>> {
+        self.corpus.get(name)
+    }
+
+    fn possible_squat(
+        &self,
+        corpus_name: &str,
+        package_name: &str,
+        package: &dyn Package,
+    ) -> Result<bool> {
+        self.corpus
+            .possible_squat(corpus_name, package_name, package)
+    }
+
+    fn index(&self) -> Option<&DeletionIndex> {
+        Some(&self.index)
+    }
+
+    fn normalizer(&self) -> Option<&dyn Normalizer> {
+        self.corpus.normalizer()
+    }
+
+    fn original_name(&self, name: &str) -> Option<&str> {
+        self.corpus.original_name(name)
+    }
+}
+
+/// A [SymSpell][symspell] style index of every name in a corpus, along with every name that can be
+/// produced by deleting up to `max_distance` characters from it.
+///
+/// This can answer "which corpus names are within `k` edits of this name" without generating
+/// every possible edit of the name: only deletions of the name being looked up need to be
+/// generated, and those are independent of the size of the alphabet.
+///
+/// Note that the index always contains single character deletions, even if `max_distance` is 0,
+/// since these are required to look up insertions and substitutions.
+///
+/// [symspell]: https://github.com/wolfgarbe/SymSpell
+pub struct DeletionIndex {
+    deletions: HashMap<String, Vec<usize>>,
+    max_distance: usize,
+    names: Vec<String>,
+}
+
+impl DeletionIndex {
+    /// Builds an index of `names`, allowing lookups of names up to `max_distance` edits away.
+    #[instrument(level = "TRACE", skip(names))]
+    pub fn new<'a>(names: impl Iterator<Item = &'a str>, max_distance: usize) -> Self {
+        let mut deletions: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut cloned_names = Vec::new();
+
+        for (i, name) in names.enumerate() {
+            cloned_names.push(name.into());
+            for deletion in deletions_of(name, max_distance.max(1)) {
+                deletions.entry(deletion).or_default().push(i);
+            }
+        }
+
+        Self {
+            deletions,
+            max_distance,
+            names: cloned_names,
+        }
+    }
+
+    /// Returns the maximum distance that can be looked up using [`DeletionIndex::within`].
+    pub fn max_distance(&self) -> usize {
+        self.max_distance
+    }
+
+    /// Checks if `name` is in the index.
+    pub fn contains(&self, name: &str) -> bool {
+        self.lookup(name).any(|candidate| candidate == name)
+    }
+
+    /// Returns every name in the index within `k` Damerau-Levenshtein edits of `name`, along with
+    /// its distance from `name`, ordered by distance and then name.
+    ///
+    /// `k` is capped at [`DeletionIndex::max_distance`]. `name` itself is included with a distance
+    /// of 0 if it is in the index.
+    pub fn within(&self, name: &str, k: usize) -> Vec<(&str, usize)> {
+        let k = k.min(self.max_distance);
+
+        let candidates: BTreeSet<&str> = deletions_of(name, k)
+            .iter()
+            .flat_map(|deletion| self.lookup(deletion))
+            .collect();
+
+        let mut found: Vec<(&str, usize)> = candidates
+            .into_iter()
+            .map(|candidate| (candidate, damerau_levenshtein(name, candidate)))
+            .filter(|(_, distance)| *distance <= k)
+            .collect();
+        found.sort_by_key(|(candidate, distance)| (*distance, *candidate));
+
+        found
+    }
+
+    /// Returns every name in the index that can be produced by inserting exactly one character
+    /// into `name`.
+    pub fn insertions<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        let len = name.chars().count() + 1;
+
+        self.lookup(name)
+            .filter(move |candidate| candidate.chars().count() == len)
+    }
+
+    /// Returns every name in the index that can be produced by substituting exactly one character
+    /// in `name`, along with the character offset of the substitution.
+    pub fn substitutions(&self, name: &str) -> Vec<(usize, &str)> {
+        let chars: Vec<char> = name.chars().collect();
+        let mut found = Vec::new();
+
+        for i in 0..chars.len() {
+            let deletion: String = chars
+                .iter()
+                .enumerate()
+                .filter_map(|(j, c)| if i == j { None } else { Some(c) })
+                .collect();
+
+            for candidate in self.lookup(&deletion) {
+                let candidate_chars: Vec<char> = candidate.chars().collect();
+                if candidate_chars.len() == chars.len()
+                    && candidate_chars[i] != chars[i]
+                    && candidate_chars
+                        .iter()
+                        .zip(chars.iter())
+                        .enumerate()
+                        .all(|(j, (a, b))| i == j || a == b)
+                {
+                    found.push((i, candidate));
+                }
+            }
+        }
+
+        found
+    }
+
+    fn lookup<'a>(&'a self, deletion: &str) -> impl Iterator<Item = &'a str> + 'a {
+        self.deletions
+            .get(deletion)
+            .into_iter()
+            .flatten()
+            .filter_map(move |index| self.names.get(*index))
+            .map(String::as_str)
+    }
+}
+
+/// Returns every unique string that can be produced by deleting up to `max` characters from
+/// `name`, including `name` itself.
+fn deletions_of(name: &str, max: usize) -> BTreeSet<String> {
+    let mut all = BTreeSet::from([String::from(name)]);
+    let mut current = all.clone();
+
+    for _ in 0..max {
+        let mut next = BTreeSet::new();
+        for s in current.iter() {
+            for (offset, c) in s.char_indices() {
+                let mut deleted = String::with_capacity(s.len());
+                deleted.push_str(&s[..offset]);
+                deleted.push_str(&s[offset + c.len_utf8()..]);
+                if !all.contains(&deleted) {
+                    next.insert(deleted);
+                }
+            }
+        }
+
+        all.extend(next.iter().cloned());
+        current = next;
+    }
+
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checks::{
+        testutil::{test_corpus, TestPackage},
+        Check, Omitted, Repeated, Typos,
+    };
+
+    use super::*;
+
+    const NAMES: &[&str] = &[
+        "serde",
+        "serde_json",
+        "tokio",
+        "rand",
+        "log",
+        "libc",
+        "syn",
+        "quote",
+        "café",
+        "日本語",
+    ];
+
+    #[test]
+    fn test_deletions_of() {
+        assert_eq!(deletions_of("ab", 0), BTreeSet::from(["ab".into()]));
+        assert_eq!(
+            deletions_of("ab", 1),
+            BTreeSet::from(["ab".into(), "a".into(), "b".into()])
+        );
+        assert_eq!(
+            deletions_of("aab", 2),
+            BTreeSet::from([
+                "aab".into(),
+                "ab".into(),
+                "aa".into(),
+                "a".into(),
+                "b".into()
+            ])
+        );
+        assert_eq!(
+            deletions_of("é日", 1),
+            BTreeSet::from(["é日".into(), "é".into(), "日".into()])
+        );
+    }
+
+    #[test]
+    fn test_within() {
+        let index = DeletionIndex::new(NAMES.iter().copied(), 2);
+
+        assert_eq!(index.within("serde", 0), vec![("serde", 0)]);
+        assert_eq!(index.within("sedre", 1), vec![("serde", 1)]);
+        assert_eq!(index.within("srd", 2), vec![("serde", 2), ("syn", 2)]);
+        assert_eq!(index.within("lig", 2), vec![("log", 1), ("libc", 2)]);
+        assert_eq!(index.within("cafe", 1), vec![("café", 1)]);
+        assert_eq!(index.within("本日語", 1), vec![("日本語", 1)]);
+
+        // k is capped at the maximum distance.
+        assert_eq!(index.within("srd", 5), vec![("serde", 2), ("syn", 2)]);
+        assert_eq!(index.within("s", 5), vec![("syn", 2)]);
+    }
+
+    #[test]
+    fn test_insertions_and_substitutions() {
+        let index = DeletionIndex::new(NAMES.iter().copied(), 1);
+
+        assert!(index.contains("serde"));
+        assert!(!index.contains("serd"));
+
+        assert_eq!(index.insertions("serd").collect::<Vec<_>>(), vec!["serde"]);
+        assert_eq!(index.insertions("日語").collect::<Vec<_>>(), vec!["日本語"]);
+        assert_eq!(index.insertions("serde").count(), 0);
+
+        assert_eq!(index.substitutions("lig"), vec![(1, "log")]);
+        assert_eq!(index.substitutions("cafe"), vec![(3, "café")]);
+        assert_eq!(index.substitutions("log"), Vec::<(usize, &str)>::new());
+    }
+
+    #[test]
+    fn test_checks_use_index() -> Result<()> {
+        #[track_caller]
+        fn test<C: Check>(check: C, input: &str) -> Result<()> {
+            let package = TestPackage::new(input);
+            let plain = test_corpus(NAMES);
+            let indexed = IndexedCorpus::new(test_corpus(NAMES), NAMES.iter().copied(), 1);
+
+            let mut want: Vec<String> = check
+                .check(&plain, input, &package)?
+                .into_iter()
+                .map(|squat| squat.package().to_string())
+                .collect();
+            want.sort();
+            want.dedup();
+
+            let mut have: Vec<String> = check
+                .check(&indexed, input, &package)?
+                .into_iter()
+                .map(|squat| squat.package().to_string())
+                .collect();
+            have.sort();
+
+            assert!(!want.is_empty());
+            assert_eq!(want, have);
+            Ok(())
+        }
+
+        test(Omitted::new("abcdefghijklmnopqrstuvwxyzé_"), "serd")?;
+        test(Omitted::new("abcdefghijklmnopqrstuvwxyzé_"), "caf")?;
+        test(Omitted::new("_"), "serdejson")?;
+        test(Repeated, "tokkio")?;
+        test(
+            Typos::new(
+                [
+                    ('i', vec![String::from("o"), String::from("")]),
+                    ('e', vec![String::from("é"), String::from("ee")]),
+                ]
+                .into_iter(),
+            ),
+            "lig",
+        )?;
+        test(
+            Typos::new([('e', vec![String::from("é"), String::from("")])].into_iter()),
+            "cafe",
+        )?;
+        test(
+            Typos::new([('x', vec![String::from("e")])].into_iter()),
+            "serdx",
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+//! The [`Corpus`] trait, and utility functions related to implementing it.
+
+use crate::{normalize::Normalizer, Package, Result};
+
+mod collections;
+mod indexed;
+mod normalized;
+
+pub use indexed::{DeletionIndex, IndexedCorpus};
+pub use normalized::Normalized;
+
+/// A corpus of existing, popular packages that checks must be run against.
+///
+/// This is implemented by default for `HashMap<String, P>` and `BTreeMap<String, P>`, where `P`
+/// implements [`Package`] (including `Box<dyn Package>`), and for `Arc`-wrapped corpora. Users
+/// with more complex needs can adapt their own package sources, provided they return [`Package`].
+pub trait Corpus: Send + Sync {
+    fn contains_name(&self, name: &str) -> Result<bool>;
+    fn get(&self, name: &str) -> Result<Option<&dyn Package>>;
+
+    /// Checks if `corpus_name` — a package in the corpus — should be considered to be squatting
+    /// package `package`, identified by `package_name`.
+    ///
+    /// This can be used to implement filters based on ecosystem-specific knowledge that isn't
+    /// exposed in the generic [`Package`] trait.
+    ///
+    /// The default implementation is [`default_possible_squat`]. Implementors replacing the
+    /// default implementation may still want to invoke [`default_possible_squat`] before adding
+    /// their own filtering.
+    fn possible_squat(
+        &self,
+        corpus_name: &str,
+        package_name: &str,
+        package: &dyn Package,
+    ) -> Result<bool> {
+        default_possible_squat(self, corpus_name, package_name, package)
+    }
+
+    /// Returns an index of the names in the corpus, if one is available.
+    ///
+    /// Checks that would otherwise generate and look up a large number of candidate names may use
+    /// the index to find matching names directly. The default implementation returns `None`; wrap
+    /// a corpus in [`IndexedCorpus`] to provide one.
+    fn index(&self) -> Option<&DeletionIndex> {
+        None
+    }
+
+    /// Returns the normalizer used to canonicalize package names in the corpus, if any.
+    ///
+    /// If this returns a normalizer, the [`Harness`][crate::Harness] normalizes the name of each
+    /// package being checked before running checks against the corpus. The default implementation
+    /// returns `None`; wrap a corpus in [`Normalized`] to provide one.
+    fn normalizer(&self) -> Option<&dyn Normalizer> {
+        None
+    }
+
+    /// Returns the name of the package with the given normalized name as it is spelled in the
+    /// corpus, if it differs.
+    ///
+    /// The [`Harness`][crate::Harness] uses this to report potential squats by the names that
+    /// packages actually have in the registry. The default implementation returns `None`.
+    fn original_name(&self, name: &str) -> Option<&str> {
+        let _ = name;
+        None
+    }
+}
+
+/// The default implementation of [`Corpus::possible_squat`], split out for easier reuse in other
+/// [`Corpus`] implementations.
+///
+/// This implementation checks four things:
+///
+/// 1. Is `corpus_name` the same as `package_name`?
+/// 1. Does the package in the corpus share any authors with `package`?
+/// 1. Has `package` been downloaded more than the package in the corpus?
+/// 1. Was `package` created before the package in the corpus?
+///
+/// If any of these checks returns true, then this function returns `false`, as it's assumed that
+/// a package cannot squat itself, that an author cannot squat their own package, and that a
+/// package cannot squat a package that is younger or less popular than it is. The last two checks
+/// are only applied if both packages provide the relevant metadata: see [`Package::downloads`] and
+/// [`Package::created_at`].
+pub fn default_possible_squat<C>(
+    corpus: &C,
+    corpus_name: &str,
+    package_name: &str,
+    package: &dyn Package,
+) -> Result<bool>
+where
+    C: Corpus + Send + Sync + ?Sized,
+{
+    Ok(if corpus_name == package_name {
+        // The same package can't squat itself.
+        false
+    } else if let Some(checked) = corpus.get(corpus_name)? {
+        // See if there are any shared authors. If not, then this might be squatted, provided the
+        // package isn't more popular or older than the package it's supposedly squatting.
+        !checked.shared_authors(package.authors())
+            && !matches!(
+                (package.downloads(), checked.downloads()),
+                (Some(squatter), Some(target)) if squatter > target
+            )
+            && !matches!(
+                (package.created_at(), checked.created_at()),
+                (Some(squatter), Some(target)) if squatter < target
+            )
+    } else {
+        false
+    })
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use crate::{normalize::Normalizer, Package, Result};
+
+use super::{Corpus, DeletionIndex};
+
+/// A [`Corpus`] wrapper that looks up packages by their normalized names.
+///
+/// Names given to [`Corpus::contains_name`], [`Corpus::get`], and [`Corpus::possible_squat`] are
+/// normalized before being looked up in the wrapped corpus, and two names with the same
+/// normalized form are never considered to squat each other.
+///
+/// The [`Harness`][crate::Harness] also normalizes the name of each package being checked before
+/// running any checks, so checks only ever see normalized names. As a result, checks that keep
+/// their own copy of the corpus names, such as [`Bitflips`][crate::checks::Bitflips], should be
+/// given the normalized names from [`Normalized::names`]. The [`Corpus::index`] of the wrapped
+/// corpus isn't used, since it contains names that haven't been normalized: to use an index, wrap
+/// this corpus in an [`IndexedCorpus`][super::IndexedCorpus] built from the normalized names. The harness reports potential
+/// squats using the names of the packages in the wrapped corpus, as returned by
+/// [`Normalized::original_name`].
+pub struct Normalized<C>
+where
+    C: Corpus,
+{
+    corpus: C,
+    names: HashMap<String, String>,
+    normalizer: Box<dyn Normalizer>,
+}
+
+impl<C> Normalized<C>
+where
+    C: Corpus,
+{
+    /// Wraps `corpus`, normalizing names using `normalizer`.
+    ///
+    /// `names` should be the full set of names in the corpus. If several names normalize to the
+    /// same canonical form, the first is used.
+    pub fn new<'a, N>(corpus: C, names: impl Iterator<Item = &'a str>, normalizer: N) -> Self
+    where
+        N: Normalizer + 'static,
+    {
+        let mut normalized = HashMap::new();
+        for name in names {
+            normalized
+                .entry(normalizer.normalize(name))
+                .or_insert_with(|| String::from(name));
+        }
+
+        Self {
+            corpus,
+            names: normalized,
+            normalizer: Box::new(normalizer),
+        }
+    }
+
+    /// Returns the normalized names in the corpus.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.keys().map(String::as_str)
+    }
+
+    /// Returns the name of the package in the wrapped corpus with the given name once normalized,
+    /// if any.
+    pub fn original_name(&self, name: &str) -> Option<&str> {
+        self.names
+            .get(&self.normalizer.normalize(name))
+            .map(String::as_str)
+    }
+
+    /// Returns the wrapped corpus.
+    pub fn into_inner(self) -> C {
+        self.corpus
+    }
+}
+
+impl<C> Corpus for Normalized<C>
+where
+    C: Corpus,
+{
+    fn contains_name(&self, name: &str) -> Result<bool> {
+        match self.original_name(name) {
+            Some(original) => self.corpus.contains_name(original),
+            None => Ok(false),
+        }
+    }
+
+    fn get(&self, name: &str) -> Result<Option<&dyn Package>> {
+        match self.original_name(name) {
+            Some(original) => self.corpus.get(original),
+            None => Ok(None),
+        }
+    }
+
+    fn possible_squat(
+        &self,
+        corpus_name: &str,
+        package_name: &str,
+        package: &dyn Package,
+    ) -> Result<bool> {
+        if self.normalizer.normalize(corpus_name) == self.normalizer.normalize(package_name) {
+            // The registry considers these to be the same package.
+            return Ok(false);
+        }
+
+        match self.original_name(corpus_name) {
+            Some(original) => self.corpus.possible_squat(original, package_name, package),
+            None => Ok(false),
+        }
+    }
+
+    fn index(&self) -> Option<&DeletionIndex> {
+        // An index of the wrapped corpus contains names that haven't been normalized, so checks
+        // would look up different names to the ones given to possible_squat.
+        None
+    }
+
+    fn normalizer(&self) -> Option<&dyn Normalizer> {
+        Some(self.normalizer.as_ref())
+    }
+
+    fn original_name(&self, name: &str) -> Option<&str> {
+        Normalized::original_name(self, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        checks::testutil::{test_corpus, TestPackage},
+        corpus::IndexedCorpus,
+        normalize::{CratesIo, Pypi},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_normalized() -> Result<()> {
+        let names = ["serde_json", "Tokio"];
+        let corpus = Normalized::new(test_corpus(&names), names.iter().copied(), CratesIo);
+
+        let mut normalized: Vec<&str> = corpus.names().collect();
+        normalized.sort();
+        assert_eq!(normalized, vec!["serde-json", "tokio"]);
+
+        assert!(corpus.contains_name("serde_json")?);
+        assert!(corpus.contains_name("serde-json")?);
+        assert!(corpus.contains_name("SERDE-JSON")?);
+        assert!(corpus.contains_name("tokio")?);
+        assert!(!corpus.contains_name("serdejson")?);
+        assert!(corpus.get("TOKIO")?.is_some());
+        assert!(corpus.get("tokyo")?.is_none());
+        assert_eq!(corpus.original_name("tokio"), Some("Tokio"));
+
+        let package = TestPackage::default();
+        assert!(!corpus.possible_squat("serde-json", "Serde_JSON", &package)?);
+        assert!(corpus.possible_squat("serde-json", "serde-jsno", &package)?);
+        assert!(!corpus.possible_squat("serde-jsno", "serde-json", &package)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalized_first_wins() {
+        let names = ["zope.interface", "Zope_Interface"];
+        let corpus = Normalized::new(test_corpus(&names), names.iter().copied(), Pypi);
+
+        assert_eq!(
+            corpus.original_name("zope-interface"),
+            Some("zope.interface")
+        );
+    }
+
+    #[test]
+    fn test_normalized_index() {
+        let names = ["Serde_Json"];
+        let indexed = IndexedCorpus::new(test_corpus(&names), names.iter().copied(), 1);
+        let corpus = Normalized::new(indexed, names.iter().copied(), CratesIo);
+
+        // The wrapped index only knows the original spelling.
+        assert!(corpus.index().is_none());
+
+        let corpus = IndexedCorpus::new(corpus, ["serde-json"].into_iter(), 1);
+        assert!(corpus
+            .index()
+            .is_some_and(|index| index.contains("serde-json")));
+    }
+}
@@ -0,0 +1,57 @@
+//! String distance functions used by checks and indices.
+
+/// Calculates the Damerau-Levenshtein distance between `a` and `b`, in characters.
+///
+/// This is the optimal string alignment variant: insertions, deletions, substitutions, and
+/// transpositions of adjacent characters each count as one edit, but no substring may be edited
+/// more than once.
+pub(crate) fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // We only ever need the previous two rows of the matrix.
+    let mut prev_prev: Vec<usize> = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            current[j] = (prev[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(prev[j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(prev_prev[j - 2] + 1);
+            }
+        }
+
+        std::mem::swap(&mut prev_prev, &mut prev);
+        std::mem::swap(&mut prev, &mut current);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        assert_eq!(damerau_levenshtein("", ""), 0);
+        assert_eq!(damerau_levenshtein("abc", ""), 3);
+        assert_eq!(damerau_levenshtein("", "abc"), 3);
+        assert_eq!(damerau_levenshtein("abc", "abc"), 0);
+        assert_eq!(damerau_levenshtein("abc", "abd"), 1);
+        assert_eq!(damerau_levenshtein("abc", "ab"), 1);
+        assert_eq!(damerau_levenshtein("abc", "abcd"), 1);
+        assert_eq!(damerau_levenshtein("abc", "acb"), 1);
+        assert_eq!(damerau_levenshtein("serde", "sedr"), 2);
+        assert_eq!(damerau_levenshtein("ca", "abc"), 3);
+        assert_eq!(damerau_levenshtein("kitten", "sitting"), 3);
+        assert_eq!(damerau_levenshtein("café", "cafe"), 1);
+        assert_eq!(damerau_levenshtein("日本語", "本日語"), 1);
+    }
+}
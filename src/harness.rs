@@ -1,4 +1,8 @@
-use std::marker::PhantomData;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    marker::PhantomData,
+    sync::{Mutex, PoisonError},
+};
 
 use itertools::Itertools;
 use thiserror::Error;
@@ -6,6 +10,9 @@ use tracing::instrument;
 
 use crate::{
     checks::{Check, Repeated, Squat, SwappedCharacters, Version},
+    corpus::DeletionIndex,
+    normalize::Normalizer,
+    score::{self, ScoredSquat, Scorer},
     BoxError, Corpus, Package,
 };
 
@@ -62,6 +69,28 @@ where
         self
     }
 
+    /// Adds a boxed check to the harness.
+    ///
+    /// This is useful when the checks to run are only known at runtime.
+    pub fn with_boxed_check(mut self, check: Box<dyn Check>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    /// Removes every check with the given [`Check::id`], such as one of the checks configured by
+    /// default by [`Harness::builder`].
+    ///
+    /// An [`Error::Configuration`] is returned if no check has the identifier.
+    pub fn without_check(mut self, id: &str) -> Result<Self, Error> {
+        remove_checks(&mut self.checks, id)?;
+        Ok(self)
+    }
+
+    /// Returns the checks that are currently configured, in the order they will run.
+    pub fn checks(&self) -> impl Iterator<Item = &dyn Check> {
+        self.checks.iter().map(|check| check.as_ref())
+    }
+
     /// Uses the given corpus to build a harness.
     pub fn build(self, corpus: C) -> Harness<C>
     where
@@ -82,7 +111,9 @@ where
     /// [`SwappedCharacters`], and [`Version`].
     ///
     /// These checks are provided by default because they don't require any specific knowledge of
-    /// the package ecosystem.
+    /// the package ecosystem. They can be removed with [`Builder::without_check`], using the
+    /// identifiers [`Repeated::ID`], [`SwappedCharacters::ID`], and [`Version::ID`], or avoided
+    /// entirely with [`Harness::empty_builder`].
     pub fn builder() -> Builder<C> {
         Builder::new()
     }
@@ -92,13 +123,26 @@ where
         Builder::empty()
     }
 
+    /// Returns the configured checks, in the order they run.
+    pub fn checks(&self) -> impl Iterator<Item = &dyn Check> {
+        self.checks.iter().map(|check| check.as_ref())
+    }
+
+    /// Removes every check with the given [`Check::id`] from the harness, and returns them in the
+    /// order they ran.
+    ///
+    /// An [`Error::Configuration`] is returned if no check has the identifier.
+    pub fn disable_check(&mut self, id: &str) -> Result<Vec<Box<dyn Check>>, Error> {
+        remove_checks(&mut self.checks, id)
+    }
+
     /// Checks all given packages against the corpus, using Rayon to parallelise the checks.
     #[cfg(feature = "rayon")]
     #[instrument(level = "DEBUG", skip_all, err)]
     pub fn check(
         &self,
         new_packages: impl Iterator<Item = (String, Box<dyn Package>)> + Send,
-    ) -> Result<std::collections::HashMap<String, Vec<Squat>>, Error> {
+    ) -> Result<HashMap<String, Vec<Squat>>, Error> {
         use rayon::prelude::*;
 
         new_packages
@@ -112,35 +156,1218 @@ where
             .collect()
     }
 
+    /// Checks all given packages against the corpus, passing the result for each package to `sink`
+    /// as soon as it is available, rather than collecting the results.
+    ///
+    /// `sink` is called once for every package, including packages with no potential squats, so
+    /// it can be used to report progress. An error checking one package doesn't prevent the other
+    /// packages from being checked: it is passed to `sink` along with the package name.
+    ///
+    /// If the `rayon` feature is enabled, packages are checked in parallel, and `sink` may be
+    /// called from several threads at once. To process results on a single thread, send them
+    /// through a channel:
+    ///
+    /// ```rust
+    /// # use std::{collections::HashMap, sync::mpsc};
+    /// # use typomania::{Harness, Package};
+    /// # let harness = Harness::builder().build(HashMap::<String, Box<dyn Package>>::new());
+    /// # let packages: Vec<(String, Box<dyn Package>)> = Vec::new();
+    /// let (tx, rx) = mpsc::sync_channel(1024);
+    /// std::thread::scope(|scope| {
+    ///     scope.spawn(move || {
+    ///         harness.check_each(packages.into_iter(), |name, result| {
+    ///             tx.send((name, result)).unwrap();
+    ///         })
+    ///     });
+    ///
+    ///     for (name, result) in rx {
+    ///         // Write the result somewhere.
+    ///     }
+    /// });
+    /// ```
+    #[instrument(level = "DEBUG", skip_all)]
+    pub fn check_each<F>(
+        &self,
+        new_packages: impl Iterator<Item = (String, Box<dyn Package>)> + Send,
+        sink: F,
+    ) where
+        F: Fn(String, Result<Vec<Squat>, Error>) + Send + Sync,
+    {
+        let check = |(name, package): (String, Box<dyn Package>)| {
+            let result = self.check_package(&name, package);
+            sink(name, result);
+        };
+
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            new_packages.par_bridge().for_each(check);
+        }
+        #[cfg(not(feature = "rayon"))]
+        new_packages.for_each(check);
+    }
+
+    /// Checks all given packages against the corpus, collecting the packages that couldn't be
+    /// checked alongside the results for the packages that could, rather than failing the whole
+    /// batch on the first error.
+    ///
+    /// Failures are keyed by package name; if a check failed, [`Error::check_id`] identifies it.
+    /// If the `rayon` feature is enabled, packages are checked in parallel.
+    #[instrument(level = "DEBUG", skip_all)]
+    pub fn check_isolated(
+        &self,
+        new_packages: impl Iterator<Item = (String, Box<dyn Package>)> + Send,
+    ) -> Outcome {
+        let outcome = Mutex::new(Outcome::default());
+
+        self.check_each(new_packages, |name, result| {
+            let mut outcome = outcome.lock().unwrap_or_else(PoisonError::into_inner);
+            match result {
+                Ok(squats) if squats.is_empty() => {}
+                Ok(squats) => {
+                    outcome.squats.insert(name, squats);
+                }
+                Err(e) => {
+                    outcome.failures.insert(name, e);
+                }
+            }
+        });
+
+        outcome.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Checks all given packages against the corpus, using Rayon to parallelise the checks, and
+    /// returns the potential squats ranked by `scorer`.
+    ///
+    /// See [`Harness::rank`] for more detail.
+    #[cfg(feature = "rayon")]
+    pub fn check_ranked(
+        &self,
+        scorer: &dyn Scorer,
+        new_packages: impl Iterator<Item = (String, Box<dyn Package>)> + Send,
+    ) -> Result<Vec<ScoredSquat>, Error> {
+        self.rank(scorer, self.check(new_packages)?)
+    }
+
+    /// Scores potential squats returned from [`Harness::check`] or [`Harness::check_package`]
+    /// using `scorer`, and returns them sorted by descending score.
+    #[instrument(level = "TRACE", skip_all, err)]
+    pub fn rank(
+        &self,
+        scorer: &dyn Scorer,
+        results: impl IntoIterator<Item = (String, Vec<Squat>)>,
+    ) -> Result<Vec<ScoredSquat>, Error> {
+        Ok(score::rank(&self.corpus, scorer, results)?)
+    }
+
     /// Checks a single package against the corpus using the configured checks.
+    ///
+    /// If the corpus provides a [`Corpus::normalizer`], the package name is normalized before
+    /// running the checks.
     #[instrument(level = "TRACE", skip(self, package), err)]
     pub fn check_package(
         &self,
         name: &str,
         package: Box<dyn Package>,
     ) -> Result<Vec<Squat>, Error> {
-        if self.corpus.contains_name(name)? {
+        let normalized = self
+            .corpus
+            .normalizer()
+            .map(|normalizer| normalizer.normalize(name));
+        let checked = normalized.as_deref().unwrap_or(name);
+
+        if self.corpus.contains_name(checked)? {
             return Ok(Vec::new());
         }
 
         self.checks
             .iter()
-            .map(|check| -> Result<Vec<Squat>, Error> {
-                Ok(check.check(&self.corpus, name, package.as_ref())?)
-            })
+            .map(|check| self.run_check(check.as_ref(), checked, package.as_ref()))
             .flatten_ok()
+            .map_ok(|squat| self.original_squat(squat))
             .collect()
     }
+
+    /// Checks every package in the corpus against every other package in the corpus, returning
+    /// the potential squats found for each package.
+    ///
+    /// `names` should be the full set of names in the corpus. Packages are checked as
+    /// [`Harness::check_package`] would check them, except that they aren't skipped for being in
+    /// the corpus, so this can find squats that have already been published.
+    ///
+    /// Each pair of packages is reported at most once: if two packages are potential squats of
+    /// each other, only the squat of the package with more downloads is reported, or, if they have
+    /// the same number of downloads, the squat by the package whose name sorts first.
+    ///
+    /// If `min_target_downloads` is given, only packages with at least that many
+    /// [`Package::downloads`] are considered to be targets of squats. Packages that don't report
+    /// their downloads are never considered to be targets in that case.
+    ///
+    /// As with [`Harness::check_package`], squatted packages are reported by their
+    /// [`Corpus::original_name`], so `names` should use the spelling of the wrapped corpus.
+    ///
+    /// If the `rayon` feature is enabled, packages are checked in parallel.
+    #[instrument(level = "DEBUG", skip_all, err)]
+    pub fn audit_corpus<'a>(
+        &self,
+        names: impl Iterator<Item = &'a str> + Send,
+        min_target_downloads: Option<u64>,
+    ) -> Result<HashMap<String, Vec<Squat>>, Error> {
+        let audit = |name: &str| -> Result<Option<(String, Vec<Squat>)>, Error> {
+            let squats = self.audit_package(name, min_target_downloads)?;
+            Ok((!squats.is_empty()).then(|| (String::from(name), squats)))
+        };
+
+        #[cfg(feature = "rayon")]
+        let results: Vec<(String, Vec<Squat>)> = {
+            use rayon::prelude::*;
+
+            names
+                .par_bridge()
+                .filter_map(|name| audit(name).transpose())
+                .collect::<Result<_, Error>>()?
+        };
+        #[cfg(not(feature = "rayon"))]
+        let results: Vec<(String, Vec<Squat>)> = names
+            .filter_map(|name| audit(name).transpose())
+            .collect::<Result<_, Error>>()?;
+
+        // Work out which packages have been reported as squatting each other.
+        let pairs: HashSet<(&str, &str)> = results
+            .iter()
+            .flat_map(|(name, squats)| {
+                squats
+                    .iter()
+                    .map(move |squat| (name.as_str(), squat.package()))
+            })
+            .collect();
+        let mut reported = HashSet::new();
+        for (name, target) in pairs.iter().copied() {
+            if pairs.contains(&(target, name)) {
+                let (name_downloads, target_downloads) = (
+                    self.downloads(name)?.unwrap_or(0),
+                    self.downloads(target)?.unwrap_or(0),
+                );
+                if (target_downloads, target) > (name_downloads, name) {
+                    reported.insert((name, target));
+                }
+            } else {
+                reported.insert((name, target));
+            }
+        }
+
+        Ok(results
+            .iter()
+            .filter_map(|(name, squats)| {
+                let squats: Vec<Squat> = squats
+                    .iter()
+                    .filter(|squat| reported.contains(&(name.as_str(), squat.package())))
+                    .cloned()
+                    .collect();
+                (!squats.is_empty()).then(|| (name.clone(), squats))
+            })
+            .collect())
+    }
+
+    /// Checks a single package in the corpus against the rest of the corpus.
+    fn audit_package(
+        &self,
+        name: &str,
+        min_target_downloads: Option<u64>,
+    ) -> Result<Vec<Squat>, Error> {
+        let normalized = self
+            .corpus
+            .normalizer()
+            .map(|normalizer| normalizer.normalize(name));
+        let checked = normalized.as_deref().unwrap_or(name);
+
+        let Some(package) = self.corpus.get(checked)? else {
+            return Ok(Vec::new());
+        };
+
+        let mut squats = Vec::new();
+        for check in self.checks.iter() {
+            for squat in self.run_check(check.as_ref(), checked, package)? {
+                let is_target = match min_target_downloads {
+                    Some(min) => self.downloads(squat.package())?.is_some_and(|d| d >= min),
+                    None => true,
+                };
+                if is_target {
+                    // Squats are keyed by the names given to the audit, so the squatted packages
+                    // have to use the same spelling for pairs to be matched up.
+                    squats.push(self.original_squat(squat));
+                }
+            }
+        }
+
+        Ok(squats)
+    }
+
+    /// Rewrites a squat to refer to the squatted package by its [`Corpus::original_name`], if it
+    /// has one.
+    fn original_squat(&self, mut squat: Squat) -> Squat {
+        if let Some(original) = self.corpus.original_name(squat.package()) {
+            if original != squat.package() {
+                let original = String::from(original);
+                squat.rename_package(&original);
+            }
+        }
+
+        squat
+    }
+
+    /// Runs a single check, attributing any error to the corpus or to the check.
+    fn run_check(
+        &self,
+        check: &dyn Check,
+        name: &str,
+        package: &dyn Package,
+    ) -> Result<Vec<Squat>, Error> {
+        let corpus = Observed::new(&self.corpus);
+        check.check(&corpus, name, package).map_err(|source| {
+            let check = String::from(check.id());
+            match source.downcast::<CorpusError>() {
+                Ok(error) => Error::Corpus {
+                    check: Some(check),
+                    source: error.0,
+                },
+                Err(source) => Error::Check { check, source },
+            }
+        })
+    }
+
+    fn downloads(&self, name: &str) -> Result<Option<u64>, Error> {
+        Ok(self
+            .corpus
+            .get(name)?
+            .and_then(|package| package.downloads()))
+    }
+
+    /// Returns every name that the configured checks would report as a potential squat of the
+    /// corpus package `package`, along with the squats that would be reported for each name.
+    ///
+    /// This is the inverse of [`Harness::check_package`], and can be used to find names worth
+    /// reserving or monitoring. Checks that can't enumerate the names they would report, such as
+    /// [`EditDistance`][crate::checks::EditDistance], are skipped, and their ids are returned in
+    /// [`SquatsOf::skipped`]; see [`Check::squats_of`] for the checks that only return some of
+    /// their names.
+    ///
+    /// If `unregistered_only` is set, names that are already in the corpus are omitted. If the
+    /// corpus provides a [`Corpus::normalizer`], names that normalize to the same name as
+    /// `package` are also omitted, since the registry considers them to be the same package.
+    #[instrument(level = "TRACE", skip(self), err)]
+    pub fn squats_of(&self, package: &str, unregistered_only: bool) -> Result<SquatsOf, Error> {
+        let normalizer = self.corpus.normalizer();
+        let normalized = normalizer.map(|normalizer| normalizer.normalize(package));
+        let mut names: BTreeMap<String, Vec<Squat>> = BTreeMap::new();
+        let mut skipped = Vec::new();
+
+        let mut squats_of = Vec::new();
+        for check in self.checks.iter() {
+            match check.squats_of(package) {
+                Some(squats) => squats_of.extend(squats),
+                None => skipped.push(String::from(check.id())),
+            }
+        }
+
+        for (name, squat) in squats_of {
+            if name == package
+                || normalizer
+                    .is_some_and(|normalizer| Some(normalizer.normalize(&name)) == normalized)
+            {
+                continue;
+            }
+
+            let squats = names.entry(name).or_default();
+            if !squats.contains(&squat) {
+                squats.push(squat);
+            }
+        }
+
+        if unregistered_only {
+            for name in names.keys().cloned().collect::<Vec<_>>() {
+                if self.corpus.contains_name(&name)? {
+                    names.remove(&name);
+                }
+            }
+        }
+
+        Ok(SquatsOf { names, skipped })
+    }
 }
 
+/// The results of [`Harness::squats_of`].
+#[derive(Debug, Default)]
+pub struct SquatsOf {
+    /// The squats that would be reported for each name, keyed by the name.
+    pub names: BTreeMap<String, Vec<Squat>>,
+
+    /// The ids of the configured checks that can't enumerate the names they would report.
+    pub skipped: Vec<String>,
+}
+
+/// The results of checking one or more packages, in a form suitable for storage.
+///
+/// When the `serde` feature is enabled, this can be serialized and deserialized. The `version`
+/// field identifies the schema of the serialized form, and will be incremented whenever the schema
+/// changes incompatibly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+    /// The schema version; see [`Report::VERSION`].
+    pub version: u32,
+
+    /// The potential squats for each checked package, keyed by package name.
+    pub packages: BTreeMap<String, Vec<Squat>>,
+}
+
+impl Report {
+    /// The current schema version.
+    pub const VERSION: u32 = 1;
+
+    /// Instantiates a report from the results of [`Harness::check`] or
+    /// [`Harness::check_package`].
+    pub fn new(results: impl IntoIterator<Item = (String, Vec<Squat>)>) -> Self {
+        Self {
+            version: Self::VERSION,
+            packages: results.into_iter().collect(),
+        }
+    }
+}
+
+impl From<HashMap<String, Vec<Squat>>> for Report {
+    fn from(value: HashMap<String, Vec<Squat>>) -> Self {
+        Self::new(value)
+    }
+}
+
+/// The results of [`Harness::check_isolated`].
+#[derive(Debug, Default)]
+pub struct Outcome {
+    /// The potential squats for each package that was checked successfully, keyed by package
+    /// name. Packages with no potential squats are omitted.
+    pub squats: HashMap<String, Vec<Squat>>,
+
+    /// The error for each package that couldn't be checked, keyed by package name.
+    pub failures: HashMap<String, Error>,
+}
+
+/// An error raised by a [`Harness`].
+///
+/// The underlying error is available through [`std::error::Error::source`].
+///
+/// When the `serde` feature is enabled, errors are serialized as objects with a `kind` field
+/// (`corpus`, `check`, or `configuration`), a `check` field with the [`Check::id`] of the check
+/// that was running, if any, and a `source` field with the message of the underlying error. Since
+/// only the message is serialized, a deserialized error's source is a plain error with that
+/// message.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
-    #[error("corpus error: {0}")]
-    Corpus(String),
+    /// The corpus returned an error. If the error was returned while a check was running, `check`
+    /// is the [`Check::id`] of that check.
+    #[error("corpus error{}: {source}", in_check(.check))]
+    Corpus {
+        check: Option<String>,
+        source: BoxError,
+    },
+
+    /// A check returned an error that didn't originate in the corpus.
+    #[error("error in check {check}: {source}")]
+    Check { check: String, source: BoxError },
+
+    /// The harness was configured incorrectly.
+    #[error("configuration error: {0}")]
+    Configuration(String),
+}
+
+impl Error {
+    /// Returns the [`Check::id`] of the check that was running when the error occurred, if any.
+    pub fn check_id(&self) -> Option<&str> {
+        match self {
+            Self::Corpus { check, .. } => check.as_deref(),
+            Self::Check { check, .. } => Some(check),
+            Self::Configuration(_) => None,
+        }
+    }
 }
 
 impl From<BoxError> for Error {
-    fn from(value: BoxError) -> Self {
-        Self::Corpus(value.to_string())
+    fn from(source: BoxError) -> Self {
+        Self::Corpus {
+            check: None,
+            source,
+        }
+    }
+}
+
+/// Removes every check with the given [`Check::id`] from `checks`, and returns them, or an
+/// [`Error::Configuration`] if there are none.
+fn remove_checks(checks: &mut Vec<Box<dyn Check>>, id: &str) -> Result<Vec<Box<dyn Check>>, Error> {
+    let (removed, kept) = std::mem::take(checks)
+        .into_iter()
+        .partition(|check| check.id() == id);
+    *checks = kept;
+
+    if removed.is_empty() {
+        Err(Error::Configuration(format!("no check with id {id}")))
+    } else {
+        Ok(removed)
+    }
+}
+
+fn in_check(check: &Option<String>) -> String {
+    match check {
+        Some(check) => format!(" in check {check}"),
+        None => String::new(),
+    }
+}
+
+#[cfg(feature = "serde")]
+mod error_serde {
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Error;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Kind {
+        Corpus,
+        Check,
+        Configuration,
+    }
+
+    #[derive(Serialize)]
+    struct Borrowed<'a> {
+        kind: Kind,
+        check: Option<&'a str>,
+        source: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Owned {
+        kind: Kind,
+        #[serde(default)]
+        check: Option<String>,
+        source: String,
+    }
+
+    impl Serialize for Error {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let (kind, source) = match self {
+                Error::Corpus { source, .. } => (Kind::Corpus, source.to_string()),
+                Error::Check { source, .. } => (Kind::Check, source.to_string()),
+                Error::Configuration(message) => (Kind::Configuration, message.clone()),
+            };
+
+            Borrowed {
+                kind,
+                check: self.check_id(),
+                source,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Error {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let Owned {
+                kind,
+                check,
+                source,
+            } = Owned::deserialize(deserializer)?;
+
+            Ok(match kind {
+                Kind::Corpus => Error::Corpus {
+                    check,
+                    source: source.into(),
+                },
+                Kind::Check => Error::Check {
+                    check: check.ok_or_else(|| de::Error::missing_field("check"))?,
+                    source: source.into(),
+                },
+                Kind::Configuration => Error::Configuration(source),
+            })
+        }
+    }
+}
+
+/// A corpus wrapper that marks the errors returned by the wrapped corpus, so that errors returned
+/// by checks can be attributed to the corpus or to the check.
+///
+/// Checks that handle a corpus error and then fail for another reason are still attributed to the
+/// check, since only the error that the check returns is inspected.
+struct Observed<'a> {
+    corpus: &'a dyn Corpus,
+}
+
+impl<'a> Observed<'a> {
+    fn new(corpus: &'a dyn Corpus) -> Self {
+        Self { corpus }
+    }
+
+    fn observe<T>(&self, result: crate::Result<T>) -> crate::Result<T> {
+        result.map_err(|source| Box::new(CorpusError(source)) as BoxError)
+    }
+}
+
+/// An error returned by the corpus wrapped by [`Observed`].
+#[derive(Error, Debug)]
+#[error(transparent)]
+struct CorpusError(BoxError);
+
+impl Corpus for Observed<'_> {
+    fn contains_name(&self, name: &str) -> crate::Result<bool> {
+        self.observe(self.corpus.contains_name(name))
+    }
+
+    fn get(&self, name: &str) -> crate::Result<Option<&dyn Package>> {
+        self.observe(self.corpus.get(name))
+    }
+
+    fn possible_squat(
+        &self,
+        corpus_name: &str,
+        package_name: &str,
+        package: &dyn Package,
+    ) -> crate::Result<bool> {
+        self.observe(
+            self.corpus
+                .possible_squat(corpus_name, package_name, package),
+        )
+    }
+
+    fn index(&self) -> Option<&DeletionIndex> {
+        self.corpus.index()
+    }
+
+    fn normalizer(&self) -> Option<&dyn Normalizer> {
+        self.corpus.normalizer()
+    }
+
+    fn original_name(&self, name: &str) -> Option<&str> {
+        self.corpus.original_name(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::{
+        checks::{
+            testutil::{test_corpus, TestPackage},
+            EditDistance, Omitted, Repeated, Scoped, SwappedCharacters, Typos,
+        },
+        corpus::Normalized,
+        normalize::{CratesIo, Npm},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_check_package_normalized() -> Result<(), Error> {
+        let names = ["serde_json", "Tokio"];
+        let harness = Harness::builder().build(Normalized::new(
+            test_corpus(&names),
+            names.iter().copied(),
+            CratesIo,
+        ));
+
+        let check = |name: &str| -> Result<Vec<String>, Error> {
+            Ok(harness
+                .check_package(name, Box::new(TestPackage::new(name)))?
+                .iter()
+                .map(|squat| squat.to_string())
+                .collect())
+        };
+
+        // The registry considers these to be the same package.
+        assert_eq!(check("Serde-JSON")?, Vec::<String>::new());
+        assert_eq!(check("tokio")?, Vec::<String>::new());
+
+        // Squats are found regardless of the form of the name, and report the name of the
+        // squatted package as it is spelled in the corpus.
+        assert_eq!(check("serde-jsno")?, vec!["swaps characters in serde_json"]);
+        assert_eq!(check("Serde_Jsno")?, vec!["swaps characters in serde_json"]);
+        assert_eq!(
+            check("TOKIO2")?,
+            vec!["only changes the version from Tokio"]
+        );
+
+        // The edit is the one found by the check, rather than one that includes differences in
+        // case or delimiters.
+        let squats = harness.check_package("TOKIO2", Box::new(TestPackage::default()))?;
+        assert_eq!(squats.len(), 1);
+        assert_eq!(squats[0].package(), "Tokio");
+        let edit = squats[0].edit().unwrap();
+        assert_eq!((edit.bytes.clone(), edit.original.as_str()), (5..6, "2"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_checks() {
+        let ids = |checks: Vec<&dyn Check>| -> Vec<String> {
+            checks
+                .into_iter()
+                .map(|check| String::from(check.id()))
+                .collect()
+        };
+
+        let builder = Harness::<HashMap<String, TestPackage>>::builder();
+        assert_eq!(
+            ids(builder.checks().collect()),
+            vec![Repeated::ID, SwappedCharacters::ID, Version::ID]
+        );
+
+        // Defaults can be replaced, but only checks that are configured can be removed.
+        let builder = builder.without_check(Version::ID).unwrap();
+        assert!(matches!(
+            builder.without_check("unknown"),
+            Err(Error::Configuration(_))
+        ));
+        let mut harness = Harness::builder()
+            .without_check(Version::ID)
+            .unwrap()
+            .with_check(Omitted::new("abc"))
+            .build(test_corpus(&["serde", "tokio"]));
+        assert_eq!(
+            ids(harness.checks().collect()),
+            vec!["repeated", "swapped-characters", "omitted"]
+        );
+        let omitted = harness.checks().last().unwrap();
+        assert_eq!(omitted.name(), "Omitted characters");
+        assert!(!omitted.description().is_empty());
+
+        // Disabled checks no longer run.
+        let check = |harness: &Harness<HashMap<String, TestPackage>>, name: &str| {
+            harness
+                .check_package(name, Box::new(TestPackage::new(name)))
+                .unwrap()
+                .len()
+        };
+        assert_eq!(check(&harness, "sedre"), 1);
+        assert_eq!(
+            harness
+                .disable_check(SwappedCharacters::ID)
+                .unwrap()
+                .iter()
+                .map(|check| check.id())
+                .collect::<Vec<_>>(),
+            vec![SwappedCharacters::ID]
+        );
+        assert_eq!(check(&harness, "sedre"), 0);
+        assert_eq!(ids(harness.checks().collect()), vec!["repeated", "omitted"]);
+
+        assert!(matches!(
+            harness.disable_check(SwappedCharacters::ID),
+            Err(Error::Configuration(_))
+        ));
+
+        // Every check with the identifier is removed.
+        let mut harness = Harness::empty_builder()
+            .with_check(Omitted::new("a"))
+            .with_check(Repeated)
+            .with_check(Omitted::new("b"))
+            .build(test_corpus(&["serde", "tokio"]));
+        assert_eq!(harness.disable_check(Omitted::ID).unwrap().len(), 2);
+        assert_eq!(ids(harness.checks().collect()), vec![Repeated::ID]);
+
+        let builder = Harness::<HashMap<String, TestPackage>>::empty_builder()
+            .with_check(Omitted::new("a"))
+            .with_check(Omitted::new("b"))
+            .without_check(Omitted::ID)
+            .unwrap();
+        assert_eq!(builder.checks().count(), 0);
+    }
+
+    #[test]
+    fn test_check_each() {
+        let harness = Harness::builder().build(test_corpus(&["serde", "tokio"]));
+        let results = Mutex::new(Vec::new());
+
+        harness.check_each(
+            ["sedre", "tokio2", "other"].into_iter().map(|name| {
+                let package: Box<dyn Package> = Box::new(TestPackage::new(name));
+                (String::from(name), package)
+            }),
+            |name, result| {
+                let squats: Vec<String> = result
+                    .unwrap()
+                    .iter()
+                    .map(|squat| squat.to_string())
+                    .collect();
+                results.lock().unwrap().push((name, squats));
+            },
+        );
+
+        let mut results = results.into_inner().unwrap();
+        results.sort();
+        assert_eq!(
+            results,
+            vec![
+                (String::from("other"), vec![]),
+                (
+                    String::from("sedre"),
+                    vec![String::from("swaps characters in serde")]
+                ),
+                (
+                    String::from("tokio2"),
+                    vec![String::from("only changes the version from tokio")]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_isolated() {
+        struct Failing;
+
+        impl Check for Failing {
+            fn check(
+                &self,
+                _corpus: &dyn Corpus,
+                name: &str,
+                _package: &dyn Package,
+            ) -> crate::Result<Vec<Squat>> {
+                if name == "bad" {
+                    Err("bad record".into())
+                } else {
+                    Ok(Vec::new())
+                }
+            }
+
+            fn id(&self) -> &str {
+                "failing"
+            }
+        }
+
+        let harness = Harness::builder()
+            .with_check(Failing)
+            .build(test_corpus(&["serde", "tokio"]));
+        let packages = || {
+            ["sedre", "bad", "tokio2", "other"].into_iter().map(|name| {
+                let package: Box<dyn Package> = Box::new(TestPackage::new(name));
+                (String::from(name), package)
+            })
+        };
+
+        // The batch fails as a whole when checked normally.
+        #[cfg(feature = "rayon")]
+        assert!(harness.check(packages()).is_err());
+
+        let outcome = harness.check_isolated(packages());
+        let mut squatted: Vec<&String> = outcome.squats.keys().collect();
+        squatted.sort();
+        assert_eq!(squatted, vec!["sedre", "tokio2"]);
+
+        assert_eq!(outcome.failures.len(), 1);
+        let error = &outcome.failures["bad"];
+        assert_eq!(error.check_id(), Some("failing"));
+        assert_eq!(error.to_string(), "error in check failing: bad record");
+    }
+
+    #[test]
+    fn test_errors() {
+        #[derive(Error, Debug)]
+        #[error("connection lost")]
+        struct Lost;
+
+        struct LostCorpus;
+
+        impl Corpus for LostCorpus {
+            fn contains_name(&self, name: &str) -> crate::Result<bool> {
+                Ok(name == "serde")
+            }
+
+            fn get(&self, _name: &str) -> crate::Result<Option<&dyn Package>> {
+                Err(Box::new(Lost))
+            }
+        }
+
+        fn assert_send_sync<T: Send + Sync + 'static>(_: &T) {}
+
+        let harness = Harness::empty_builder()
+            .with_check(SwappedCharacters)
+            .build(LostCorpus);
+        let error = harness
+            .check_package("sedre", Box::new(TestPackage::default()))
+            .unwrap_err();
+        assert_send_sync(&error);
+
+        // The error is attributed to the corpus, and the check that was running is recorded.
+        let Error::Corpus {
+            check: Some(check),
+            source,
+        } = &error
+        else {
+            panic!("unexpected error {error:?}");
+        };
+        assert_eq!(check, "swapped-characters");
+        assert!(source.is::<Lost>());
+        assert!(std::error::Error::source(&error).is_some_and(|source| source.is::<Lost>()));
+        assert_eq!(
+            error.to_string(),
+            format!("corpus error in check {check}: connection lost")
+        );
+
+        // Checks that recover from a corpus error and then fail on their own are attributed to
+        // the check.
+        struct Recovering;
+
+        impl Check for Recovering {
+            fn check(
+                &self,
+                corpus: &dyn Corpus,
+                name: &str,
+                _package: &dyn Package,
+            ) -> crate::Result<Vec<Squat>> {
+                if corpus.get(name).is_err() {
+                    Err("no fallback".into())
+                } else {
+                    Ok(Vec::new())
+                }
+            }
+
+            fn id(&self) -> &str {
+                "recovering"
+            }
+        }
+
+        let error = Harness::empty_builder()
+            .with_check(Recovering)
+            .build(LostCorpus)
+            .check_package("sedre", Box::new(TestPackage::default()))
+            .unwrap_err();
+        assert!(matches!(&error, Error::Check { check, .. } if check == "recovering"));
+        assert_eq!(error.to_string(), "error in check recovering: no fallback");
+
+        // Errors outside of checks aren't attributed to a check.
+        let error = harness
+            .audit_corpus(["serde"].into_iter(), None)
+            .unwrap_err();
+        assert!(matches!(error, Error::Corpus { check: None, .. }));
+    }
+
+    #[test]
+    fn test_check_package_scoped_normalized() -> Result<(), Error> {
+        let names = ["@Types/Node"];
+        let harness = Harness::empty_builder()
+            .with_check(Scoped::new().with_check(Omitted::new("e")))
+            .build(Normalized::new(
+                test_corpus(&names),
+                names.iter().copied(),
+                Npm,
+            ));
+
+        // Both the scoped name and the part squatted by the inner check use the corpus spelling.
+        let squats = harness.check_package("@types/nod", Box::new(TestPackage::default()))?;
+        match squats.as_slice() {
+            [Squat::Scope {
+                details,
+                inner: Some(inner),
+            }] => {
+                assert_eq!(details.package, "@Types/Node");
+                assert_eq!(inner.package(), "Node");
+            }
+            _ => panic!("unexpected squats {squats:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_audit_corpus() -> Result<(), Error> {
+        let package = |downloads| TestPackage {
+            downloads,
+            ..TestPackage::default()
+        };
+        let corpus: HashMap<String, TestPackage> = [
+            ("serde", package(Some(1000))),
+            ("sedre", package(Some(10))),
+            ("serde2", package(Some(1))),
+            ("abc", package(None)),
+            ("bac", package(None)),
+        ]
+        .into_iter()
+        .map(|(name, package)| (String::from(name), package))
+        .collect();
+        let names: Vec<String> = corpus.keys().cloned().collect();
+        let harness = Harness::builder().build(corpus);
+
+        let audit = |min_target_downloads| -> Result<Vec<(String, Vec<String>)>, Error> {
+            let mut results: Vec<(String, Vec<String>)> = harness
+                .audit_corpus(names.iter().map(String::as_str), min_target_downloads)?
+                .into_iter()
+                .map(|(name, squats)| {
+                    (name, squats.iter().map(|squat| squat.to_string()).collect())
+                })
+                .collect();
+            results.sort();
+            Ok(results)
+        };
+
+        assert_eq!(
+            audit(None)?,
+            vec![
+                (
+                    String::from("abc"),
+                    vec![String::from("swaps characters in bac")]
+                ),
+                (
+                    String::from("sedre"),
+                    vec![String::from("swaps characters in serde")]
+                ),
+                (
+                    String::from("serde2"),
+                    vec![String::from("only changes the version from serde")]
+                ),
+            ]
+        );
+        assert_eq!(
+            audit(Some(100))?,
+            vec![
+                (
+                    String::from("sedre"),
+                    vec![String::from("swaps characters in serde")]
+                ),
+                (
+                    String::from("serde2"),
+                    vec![String::from("only changes the version from serde")]
+                ),
+            ]
+        );
+        assert_eq!(audit(Some(10_000))?, vec![]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_audit_corpus_normalized() -> Result<(), Error> {
+        let package = |downloads| TestPackage {
+            downloads: Some(downloads),
+            ..TestPackage::default()
+        };
+        let corpus: HashMap<String, TestPackage> =
+            [("Serde_Json", package(1000)), ("serde_jsno", package(10))]
+                .into_iter()
+                .map(|(name, package)| (String::from(name), package))
+                .collect();
+        let names: Vec<String> = corpus.keys().cloned().collect();
+        let harness = Harness::empty_builder()
+            .with_check(SwappedCharacters)
+            .build(Normalized::new(
+                corpus,
+                names.iter().map(String::as_str),
+                CratesIo,
+            ));
+
+        // The packages squat each other, but only the squat of the more popular package is
+        // reported, using the names from the corpus.
+        let results = harness.audit_corpus(names.iter().map(String::as_str), None)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results["serde_jsno"]
+                .iter()
+                .map(|squat| squat.to_string())
+                .collect::<Vec<_>>(),
+            vec!["swaps characters in Serde_Json"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_squats_of() -> Result<(), Error> {
+        let harness = Harness::empty_builder()
+            .with_check(Repeated)
+            .with_check(SwappedCharacters)
+            .with_check(EditDistance::new(1, ["abc", "bac"].into_iter()))
+            .with_check(Omitted::new("a"))
+            .build(test_corpus(&["abc", "bac"]));
+
+        let names = |unregistered_only| -> Result<Vec<String>, Error> {
+            Ok(harness
+                .squats_of("abc", unregistered_only)?
+                .names
+                .into_keys()
+                .collect())
+        };
+
+        assert_eq!(
+            names(false)?,
+            vec!["aabc", "abbc", "abcc", "acb", "bac", "bc"]
+        );
+        assert_eq!(names(true)?, vec!["aabc", "abbc", "abcc", "acb", "bc"]);
+
+        let squats = harness.squats_of("abc", false)?;
+        assert_eq!(squats.skipped, vec!["edit-distance"]);
+        assert_eq!(
+            squats.names["bc"]
+                .iter()
+                .map(|squat| squat.to_string())
+                .collect::<Vec<_>>(),
+            vec!["omits characters in abc"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_squats_of_normalized() -> Result<(), Error> {
+        let names = ["a-b"];
+        let harness = Harness::empty_builder()
+            .with_check(Typos::new(
+                [
+                    ('_', vec![String::from("-")]),
+                    ('c', vec![String::from("b")]),
+                ]
+                .into_iter(),
+            ))
+            .build(Normalized::new(
+                test_corpus(&names),
+                names.iter().copied(),
+                CratesIo,
+            ));
+
+        // a_b is the same crate as a-b, so can't squat it.
+        assert_eq!(
+            harness
+                .squats_of("a-b", false)?
+                .names
+                .into_keys()
+                .collect::<Vec<_>>(),
+            vec!["a-c"]
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_error_serde() -> Result<(), serde_json::Error> {
+        #[track_caller]
+        fn test(error: Error, want: serde_json::Value) -> Result<(), serde_json::Error> {
+            let json = serde_json::to_value(&error)?;
+            assert_eq!(json, want);
+
+            let deserialized: Error = serde_json::from_value(json)?;
+            assert_eq!(deserialized.to_string(), error.to_string());
+            assert_eq!(deserialized.check_id(), error.check_id());
+            assert_eq!(serde_json::to_value(&deserialized)?, want);
+            Ok(())
+        }
+
+        test(
+            Error::from(BoxError::from("unavailable")),
+            serde_json::json!({"kind": "corpus", "check": null, "source": "unavailable"}),
+        )?;
+        test(
+            Error::Corpus {
+                check: Some(String::from("typos")),
+                source: "unavailable".into(),
+            },
+            serde_json::json!({"kind": "corpus", "check": "typos", "source": "unavailable"}),
+        )?;
+        test(
+            Error::Check {
+                check: String::from("typos"),
+                source: "bad table".into(),
+            },
+            serde_json::json!({"kind": "check", "check": "typos", "source": "bad table"}),
+        )?;
+        test(
+            Error::Configuration(String::from("no check with id typos")),
+            serde_json::json!({
+                "kind": "configuration",
+                "check": null,
+                "source": "no check with id typos",
+            }),
+        )?;
+
+        // Check errors can't be deserialized without a check.
+        assert!(serde_json::from_value::<Error>(
+            serde_json::json!({"kind": "check", "source": "bad table"})
+        )
+        .is_err());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_report_serde() -> Result<(), serde_json::Error> {
+        use crate::checks::{Details, FlippedBit};
+
+        let report = Report::new([
+            (
+                String::from("ac"),
+                vec![Squat::Bitflip {
+                    details: Details::new("ac", "ab"),
+                    flip: FlippedBit { byte: 1, bit: 0 },
+                }],
+            ),
+            (
+                String::from("serd"),
+                vec![
+                    Squat::OmittedCharacter(Details::new("serd", "serde")),
+                    Squat::custom("custom", "serde"),
+                ],
+            ),
+        ]);
+
+        let json = serde_json::to_value(&report)?;
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "version": 1,
+                "packages": {
+                    "ac": [{
+                        "type": "bitflip",
+                        "details": {
+                            "package": "ab",
+                            "edit": {
+                                "bytes": {"start": 1, "end": 2},
+                                "chars": {"start": 1, "end": 2},
+                                "original": "c",
+                                "replacement": "b",
+                            },
+                        },
+                        "flip": {"byte": 1, "bit": 0},
+                    }],
+                    "serd": [
+                        {
+                            "type": "omitted-character",
+                            "details": {
+                                "package": "serde",
+                                "edit": {
+                                    "bytes": {"start": 4, "end": 4},
+                                    "chars": {"start": 4, "end": 4},
+                                    "original": "",
+                                    "replacement": "e",
+                                },
+                            },
+                        },
+                        {
+                            "type": "custom",
+                            "message": "custom",
+                            "details": {"package": "serde", "edit": null},
+                        },
+                    ],
+                },
+            })
+        );
+
+        assert_eq!(serde_json::from_value::<Report>(json)?, report);
+
+        Ok(())
     }
 }
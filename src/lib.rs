@@ -12,9 +12,21 @@
 //! techniques. Custom checks may also be written by implementing [`checks::Check`]; custom checks
 //! should use [`checks::Squat::Custom`] when returning potential typosquats.
 //!
+//! Checking a package against a very large corpus can be slow, since most checks generate and
+//! look up a large number of candidate names. Wrapping the corpus in a [`corpus::IndexedCorpus`]
+//! allows checks that support it to query an index of nearby names instead.
+//!
+//! Registries often treat several spellings of a name as the same package. Wrapping the corpus in a
+//! [`corpus::Normalized`] corpus with one of the [`normalize::Normalizer`] implementations for
+//! common registries ensures that those names are not reported as squats of each other.
+//!
 //! A [`Harness`] is provided that can be used to run a suite of checks against a single package,
 //! or — when the `rayon` feature is enabled — against many packages at once in parallel.
 //!
+//! The harness returns potential squats in no particular order. A [`score::Scorer`] can be used
+//! with [`Harness::rank`] to assign each potential squat a risk score and sort them, so that the
+//! most likely squats can be reviewed first.
+//!
 //! Checks and corpora both use instances of [`Package`], which provides a basic lowest common
 //! denominator representation of ecosystem-specific packages. Users are expected to implement
 //! [`Package`] (and the related [`AuthorSet`]) on their native package type for analysis.
@@ -30,11 +42,18 @@
 
 pub mod checks;
 pub mod corpus;
+mod distance;
 mod error;
 mod harness;
+pub mod normalize;
 mod package;
+mod phonetic;
+pub mod score;
 
 pub use corpus::Corpus;
 pub use error::{BoxError, Result};
-pub use harness::{Builder as HarnessBuilder, Error as HarnessError, Harness};
+pub use harness::{
+    Builder as HarnessBuilder, Error as HarnessError, Harness, Outcome as HarnessOutcome,
+    Report as HarnessReport, SquatsOf as HarnessSquatsOf,
+};
 pub use package::{AuthorSet, Package};
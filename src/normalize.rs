@@ -0,0 +1,105 @@
+//! Registry specific package name normalization.
+//!
+//! Most registries consider some package names to be equivalent: for example, crates.io treats
+//! `serde_json` and `Serde-JSON` as the same crate. A [`Normalizer`] reduces a name to its
+//! canonical form, so that names the registry treats as identical aren't reported as potential
+//! squats of each other.
+//!
+//! To use a normalizer, wrap the corpus in a [`Normalized`][crate::corpus::Normalized] corpus.
+
+/// Reduces a package name to a canonical form.
+///
+/// Two names with the same canonical form are considered to be the same package.
+pub trait Normalizer: Send + Sync {
+    fn normalize(&self, name: &str) -> String;
+}
+
+/// Normalizes crate names as crates.io does: names are case insensitive, and `-` and `_` are
+/// equivalent.
+///
+/// Names are normalized to lowercase, with `_` replaced by `-`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CratesIo;
+
+impl Normalizer for CratesIo {
+    fn normalize(&self, name: &str) -> String {
+        name.to_lowercase().replace('_', "-")
+    }
+}
+
+/// Normalizes package names as PyPI does, per [PEP 503][pep503]: names are case insensitive, and
+/// runs of `-`, `_`, and `.` are equivalent.
+///
+/// Names are normalized to lowercase, with each run of delimiters replaced by a single `-`.
+///
+/// [pep503]: https://peps.python.org/pep-0503/#normalized-names
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pypi;
+
+impl Normalizer for Pypi {
+    fn normalize(&self, name: &str) -> String {
+        let mut normalized = String::with_capacity(name.len());
+        let mut in_delimiter = false;
+
+        for c in name.chars() {
+            if matches!(c, '-' | '_' | '.') {
+                if !in_delimiter {
+                    normalized.push('-');
+                }
+                in_delimiter = true;
+            } else {
+                normalized.extend(c.to_lowercase());
+                in_delimiter = false;
+            }
+        }
+
+        normalized
+    }
+}
+
+/// Normalizes package names as npm does: names are case insensitive, including the scope of
+/// scoped packages.
+///
+/// Names are normalized to lowercase, with surrounding whitespace removed. The `@scope/` prefix of
+/// scoped packages is preserved.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Npm;
+
+impl Normalizer for Npm {
+    fn normalize(&self, name: &str) -> String {
+        name.trim().to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crates_io() {
+        assert_eq!(CratesIo.normalize("serde"), "serde");
+        assert_eq!(CratesIo.normalize("serde_json"), "serde-json");
+        assert_eq!(CratesIo.normalize("Serde-JSON"), "serde-json");
+        assert_eq!(CratesIo.normalize("a__b"), "a--b");
+        assert_eq!(CratesIo.normalize("foo.bar"), "foo.bar");
+    }
+
+    #[test]
+    fn test_pypi() {
+        assert_eq!(Pypi.normalize("requests"), "requests");
+        assert_eq!(Pypi.normalize("Django"), "django");
+        assert_eq!(Pypi.normalize("zope.interface"), "zope-interface");
+        assert_eq!(Pypi.normalize("friendly-bard"), "friendly-bard");
+        assert_eq!(Pypi.normalize("Friendly._-Bard"), "friendly-bard");
+        assert_eq!(Pypi.normalize("FRIENDLY_BARD"), "friendly-bard");
+    }
+
+    #[test]
+    fn test_npm() {
+        assert_eq!(Npm.normalize("lodash"), "lodash");
+        assert_eq!(Npm.normalize("JSONStream"), "jsonstream");
+        assert_eq!(Npm.normalize("@Babel/Core"), "@babel/core");
+        assert_eq!(Npm.normalize(" left-pad "), "left-pad");
+        assert_eq!(Npm.normalize("lodash_merge"), "lodash_merge");
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::SystemTime;
+
 /// Common trait that packages must implement to provide common metadata used by checks and
 /// corpora.
 ///
@@ -43,6 +45,63 @@ pub trait Package: Send + Sync {
 
     /// Checks if any authors on the other [`AuthorSet`] match any authors on this package.
     fn shared_authors(&self, other: &dyn AuthorSet) -> bool;
+
+    /// Returns the number of times the package has been downloaded, if known.
+    ///
+    /// Typosquats tend to target packages that are much more popular than they are, so this is
+    /// used by [`default_possible_squat`][crate::corpus::default_possible_squat] and
+    /// [`Weighted`][crate::score::Weighted]. The default implementation returns `None`.
+    fn downloads(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the time the package was first published, if known.
+    ///
+    /// Typosquats tend to be much younger than the packages they target, so this is used by
+    /// [`default_possible_squat`][crate::corpus::default_possible_squat]. The default
+    /// implementation returns `None`.
+    fn created_at(&self) -> Option<SystemTime> {
+        None
+    }
+
+    /// Returns the time the latest release of the package was published, if known.
+    ///
+    /// This isn't used by any check shipped by default in typomania. The default implementation
+    /// returns `None`.
+    fn latest_release_at(&self) -> Option<SystemTime> {
+        None
+    }
+}
+
+/// Forwards to the boxed package, so that corpora can hold packages of different types as
+/// `Box<dyn Package>`.
+impl<P> Package for Box<P>
+where
+    P: Package + ?Sized,
+{
+    fn authors(&self) -> &dyn AuthorSet {
+        self.as_ref().authors()
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.as_ref().description()
+    }
+
+    fn shared_authors(&self, other: &dyn AuthorSet) -> bool {
+        self.as_ref().shared_authors(other)
+    }
+
+    fn downloads(&self) -> Option<u64> {
+        self.as_ref().downloads()
+    }
+
+    fn created_at(&self) -> Option<SystemTime> {
+        self.as_ref().created_at()
+    }
+
+    fn latest_release_at(&self) -> Option<SystemTime> {
+        self.as_ref().latest_release_at()
+    }
 }
 
 /// Trait that packages must implement to check if they have a particular author.
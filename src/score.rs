@@ -0,0 +1,378 @@
+//! Scoring and ranking of potential typosquats.
+//!
+//! Checks report every potential squat they find with equal weight, which can result in a lot of
+//! noise when checking a large number of packages: a one character name will be a possible squat
+//! of dozens of other names. A [`Scorer`] assigns each [`Squat`] a numeric risk score, which
+//! [`Harness::rank`][crate::Harness::rank] then uses to order results so that the most likely
+//! squats can be reviewed first.
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{
+    checks::{
+        Affixes, Bitflips, Chain, Delimiters, EditDistance, Homoglyphs, Inflections, Leetspeak,
+        Omitted, Phonetic, Repeated, Scoped, Squat, SwappedCharacters, SwappedWords, Typos,
+        Version,
+    },
+    distance::damerau_levenshtein,
+    Corpus,
+};
+
+/// A potential squat, along with the name of the checked package and its risk score.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoredSquat {
+    /// The name of the package that was checked.
+    pub name: String,
+
+    /// The potential squat.
+    pub squat: Squat,
+
+    /// The risk score of the potential squat; higher scores indicate higher risk.
+    pub score: f64,
+}
+
+/// Assigns a risk score to a potential squat.
+///
+/// Scores are only meaningful relative to other scores from the same scorer: higher scores
+/// indicate that the squat is more likely to be malicious, and should be reviewed sooner.
+pub trait Scorer: Send + Sync {
+    fn score(&self, corpus: &dyn Corpus, name: &str, squat: &Squat) -> crate::Result<f64>;
+}
+
+type Popularity = Box<dyn Fn(&str) -> Option<u64> + Send + Sync>;
+
+/// The default [`Scorer`], which combines several signals into a single score.
+///
+/// The score is the product of:
+///
+/// 1. A weight for the type of check that produced the squat (see [`Weighted::with_weight`]).
+/// 1. The reciprocal of the number of edits the check made to the squatted package name, so that
+///    closer names score higher. Most checks make a single logical edit, even if it changes many
+///    characters, such as swapping two words; [`Squat::Chain`] counts one edit per step, and
+///    [`Squat::EditDistance`] uses its distance. The Damerau-Levenshtein distance between the
+///    names is only used for [`Squat::Custom`], whose edits aren't known.
+/// 1. A length factor, which scales the score down for names shorter than a given length (see
+///    [`Weighted::with_full_length`]), since short names are within a small edit distance of many
+///    other names.
+/// 1. A popularity factor of `1 + log10(1 + downloads)` for the squatted package, if its
+///    popularity is known. By default, this uses [`Package::downloads`][crate::Package::downloads]
+///    from the corpus; see [`Weighted::with_popularity`] to override this.
+pub struct Weighted {
+    weights: HashMap<String, f64>,
+    default_weight: f64,
+    full_length: usize,
+    popularity: Option<Popularity>,
+}
+
+impl Weighted {
+    /// Instantiates a scorer with the default weights and a full length of 8 characters.
+    pub fn new() -> Self {
+        Self {
+            weights: DEFAULT_WEIGHTS
+                .iter()
+                .map(|(check, weight)| (String::from(*check), *weight))
+                .collect(),
+            default_weight: 1.0,
+            full_length: 8,
+            popularity: None,
+        }
+    }
+
+    /// Sets the weight applied to squats produced by the given check, identified by the value
+    /// returned from [`Squat::check`].
+    ///
+    /// Checks without a weight — including custom checks — have a weight of 1.0.
+    pub fn with_weight(mut self, check: &str, weight: f64) -> Self {
+        self.weights.insert(check.into(), weight);
+        self
+    }
+
+    /// Sets the name length, in characters, at and above which no length penalty is applied.
+    ///
+    /// Names shorter than this have their score scaled down linearly.
+    pub fn with_full_length(mut self, full_length: usize) -> Self {
+        self.full_length = full_length;
+        self
+    }
+
+    /// Sets a function that returns the popularity of a package in the corpus, typically as a
+    /// download count.
+    ///
+    /// If no function is set, then [`Package::downloads`][crate::Package::downloads] is used for
+    /// the package in the corpus. If the popularity is unknown, the popularity factor is 1.
+    pub fn with_popularity<F>(mut self, popularity: F) -> Self
+    where
+        F: Fn(&str) -> Option<u64> + Send + Sync + 'static,
+    {
+        self.popularity = Some(Box::new(popularity));
+        self
+    }
+}
+
+impl Default for Weighted {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scorer for Weighted {
+    fn score(&self, corpus: &dyn Corpus, name: &str, squat: &Squat) -> crate::Result<f64> {
+        let weight = self
+            .weights
+            .get(squat.check())
+            .copied()
+            .unwrap_or(self.default_weight);
+
+        let distance = edits(name, squat);
+
+        let length = name.chars().count().min(squat.package().chars().count());
+        let length_factor = if self.full_length == 0 {
+            1.0
+        } else {
+            length.min(self.full_length) as f64 / self.full_length as f64
+        };
+
+        let downloads = match &self.popularity {
+            Some(popularity) => popularity(squat.package()),
+            None => corpus
+                .get(squat.package())?
+                .and_then(|package| package.downloads()),
+        };
+        let popularity_factor = downloads
+            .map(|downloads| 1.0 + (1.0 + downloads as f64).log10())
+            .unwrap_or(1.0);
+
+        Ok(weight * length_factor * popularity_factor / distance.max(1) as f64)
+    }
+}
+
+/// Returns the number of logical edits that `squat` made to produce `name`.
+fn edits(name: &str, squat: &Squat) -> usize {
+    match squat {
+        Squat::Chain { steps, .. } => steps.len(),
+        Squat::EditDistance { distance, .. } => *distance,
+        Squat::Scope {
+            inner: Some(inner), ..
+        } => edits(name, inner),
+        Squat::Custom { .. } => damerau_levenshtein(name, squat.package()),
+        _ => 1,
+    }
+}
+
+// Weights for the built-in checks. Checks that are more prone to false positives are weighted
+// lower.
+static DEFAULT_WEIGHTS: &[(&str, f64)] = &[
+    (Affixes::ID, 1.0),
+    (Bitflips::ID, 0.5),
+    (Chain::ID, 0.5),
+    (Delimiters::ID, 0.9),
+    (EditDistance::ID, 0.6),
+    (Homoglyphs::ID, 1.0),
+    (Inflections::ID, 0.7),
+    (Leetspeak::ID, 0.9),
+    (Omitted::ID, 0.9),
+    (Phonetic::ID, 0.7),
+    (Repeated::ID, 0.8),
+    (Scoped::ID, 0.9),
+    (SwappedCharacters::ID, 0.9),
+    (SwappedWords::ID, 0.7),
+    (Typos::ID, 0.8),
+    (Version::ID, 0.6),
+];
+
+/// Scores every squat in `results` and returns them sorted by descending score.
+///
+/// Squats with equal scores are ordered by checked package name, then squatted package name.
+pub fn rank(
+    corpus: &dyn Corpus,
+    scorer: &dyn Scorer,
+    results: impl IntoIterator<Item = (String, Vec<Squat>)>,
+) -> crate::Result<Vec<ScoredSquat>> {
+    let mut scored = Vec::new();
+    for (name, squats) in results.into_iter() {
+        for squat in squats.into_iter() {
+            let score = scorer.score(corpus, &name, &squat)?;
+            scored.push(ScoredSquat {
+                name: name.clone(),
+                squat,
+                score,
+            });
+        }
+    }
+
+    scored.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.squat.package().cmp(b.squat.package()))
+    });
+
+    Ok(scored)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::checks::{testutil::TestPackage, Details};
+
+    use super::*;
+
+    #[track_caller]
+    fn assert_score(scorer: &Weighted, name: &str, squat: Squat, want: f64) {
+        assert_corpus_score(
+            scorer,
+            &HashMap::<String, TestPackage>::new(),
+            name,
+            squat,
+            want,
+        )
+    }
+
+    #[track_caller]
+    fn assert_corpus_score(
+        scorer: &Weighted,
+        corpus: &dyn Corpus,
+        name: &str,
+        squat: Squat,
+        want: f64,
+    ) {
+        let have = scorer.score(corpus, name, &squat).unwrap();
+        assert!((have - want).abs() < 1e-9, "{have} != {want}");
+    }
+
+    #[test]
+    fn test_weighted() {
+        let scorer = Weighted::new();
+        let typo = || Squat::Typo(Details::new("lig", "log"));
+
+        // Full length names with a single edit are scored by the check weight alone.
+        assert_score(
+            &scorer,
+            "serde-json",
+            Squat::Typo(Details::new("serde-json", "serde_json")),
+            0.8,
+        );
+        assert_score(
+            &scorer,
+            "serde-json",
+            Squat::custom("custom", "serde_json"),
+            1.0,
+        );
+
+        // Checks that make a single logical edit aren't penalised for changing many characters.
+        assert_score(
+            &scorer,
+            "json-serde",
+            Squat::SwappedWords(Details::new("json-serde", "serde-json")),
+            0.7,
+        );
+        assert_score(
+            &scorer,
+            "serde-json2",
+            Squat::Version(Details::new("serde-json2", "serde-json")),
+            0.6,
+        );
+
+        // Chains count one edit per step.
+        assert_score(
+            &scorer,
+            "sedre-json",
+            Squat::Chain {
+                details: Details::new("sedre-json", "serde_json"),
+                steps: vec![
+                    Squat::SwappedCharacters(Details::new("sedre-json", "serde-json")),
+                    Squat::Delimiter(Details::new("serde-json", "serde_json")),
+                ],
+            },
+            0.25,
+        );
+
+        // Custom squats fall back to the distance between the names.
+        assert_score(
+            &scorer,
+            "serde-jsno",
+            Squat::custom("custom", "sedre-json"),
+            0.5,
+        );
+
+        // Short names are penalised.
+        assert_score(&scorer, "lig", typo(), 0.3);
+
+        // Larger distances are penalised.
+        assert_score(
+            &scorer,
+            "tokio-tset",
+            Squat::EditDistance {
+                details: Details::new("tokio-tset", "tokio-test"),
+                distance: 2,
+            },
+            0.3,
+        );
+
+        // Weights and lengths can be configured.
+        let scorer = Weighted::new()
+            .with_weight("typos", 2.0)
+            .with_full_length(2);
+        assert_score(&scorer, "lig", typo(), 2.0);
+
+        // Popular packages score higher.
+        let scorer =
+            Weighted::new().with_popularity(|name| if name == "log" { Some(99) } else { None });
+        assert_score(&scorer, "lig", typo(), 0.9);
+
+        // By default, popularity comes from the corpus.
+        let corpus = HashMap::from([(
+            String::from("log"),
+            TestPackage {
+                downloads: Some(99),
+                ..TestPackage::default()
+            },
+        )]);
+        assert_corpus_score(&Weighted::new(), &corpus, "lig", typo(), 0.9);
+        assert_corpus_score(
+            &Weighted::new().with_popularity(|_| None),
+            &corpus,
+            "lig",
+            typo(),
+            0.3,
+        );
+    }
+
+    #[test]
+    fn test_rank() -> crate::Result<()> {
+        let results = vec![
+            (
+                String::from("lig"),
+                vec![Squat::Typo(Details::new("lig", "log"))],
+            ),
+            (
+                String::from("tokio-tset"),
+                vec![
+                    Squat::SwappedCharacters(Details::new("tokio-tset", "tokio-test")),
+                    Squat::Version(Details::new("tokio-tset", "tokio")),
+                ],
+            ),
+        ];
+
+        let ranked: Vec<(String, String)> = rank(
+            &HashMap::<String, TestPackage>::new(),
+            &Weighted::new(),
+            results,
+        )?
+        .into_iter()
+        .map(|scored| (scored.name, scored.squat.package().to_string()))
+        .collect();
+
+        assert_eq!(
+            ranked,
+            vec![
+                ("tokio-tset".into(), "tokio-test".into()),
+                ("tokio-tset".into(), "tokio".into()),
+                ("lig".into(), "log".into()),
+            ]
+        );
+
+        Ok(())
+    }
+}